@@ -0,0 +1,14 @@
+use fast_log::plugin::packer::{decrypt_file, parse_key};
+
+/// decrypt an archive of EncryptPacker
+/// cargo run --bin decrypt_log -- <key hex> <archive path> <out path>
+fn main() {
+    let args: Vec<String> = std::env::args().collect();
+    if args.len() != 4 {
+        println!("usage: decrypt_log <key hex> <archive path> <out path>");
+        return;
+    }
+    let key = parse_key(&args[1]).unwrap();
+    decrypt_file(&key, &args[2], &args[3]).unwrap();
+    println!("decrypted into {}", args[3]);
+}
@@ -8,7 +8,7 @@ fn main() {
         "target/logs/",
         Rolling::new(RollingType::BySize(LogSize::KB(50))),
         KeepType::KeepNum(5),
-        GZipPacker {},
+        GZipPacker::new().level(9),
     ))
     .unwrap();
     for _ in 0..20000 {
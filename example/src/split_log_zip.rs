@@ -9,7 +9,7 @@ fn main() {
         "target/logs/",
         Rolling::new(RollingType::BySize(LogSize::KB(50))),
         KeepType::KeepNum(5),
-        ZipPacker {},
+        ZipPacker::new().level(9).bundle_daily(),
     ))
     .unwrap();
     for _ in 0..20000 {
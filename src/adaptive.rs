@@ -0,0 +1,156 @@
+use crate::appender::{Command, FastLogRecord};
+use crate::{logger, spawn};
+use log::{Level, LevelFilter};
+use parking_lot::Mutex;
+use std::collections::VecDeque;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+
+/// adaptive verbosity.
+/// watch the rate of WARN/ERROR records,when more than `threshold` records arrive within `window`,
+/// set the level to `level` for `period` then restore it(unless the level is changed by others meanwhile).
+/// the level is changed by `Logger::set_level`, and each change is announced by an record of target `fast_log`.
+/// for example:
+/// ```rust
+/// use std::time::Duration;
+/// use log::LevelFilter;
+/// use fast_log::Config;
+/// use fast_log::adaptive::AdaptiveLevel;
+/// fn main(){
+///    fast_log::init(Config::new().console().level(LevelFilter::Info).adaptive_level(
+///        AdaptiveLevel::new(10, Duration::from_secs(1), LevelFilter::Debug, Duration::from_secs(60)),
+///    )).unwrap();
+/// }
+/// ```
+pub struct AdaptiveLevel {
+    /// records at this level or more severe are counted, default Warn
+    pub watch_level: Level,
+    /// max records of window
+    pub threshold: usize,
+    pub window: Duration,
+    /// the level used when the threshold exceeded
+    pub level: LevelFilter,
+    /// how long the level keep
+    pub period: Duration,
+    state: Arc<Mutex<AdaptiveState>>,
+}
+
+struct AdaptiveState {
+    hits: VecDeque<SystemTime>,
+    /// Some(restore level) when the level is changed
+    restore: Option<LevelFilter>,
+    until: SystemTime,
+}
+
+impl AdaptiveLevel {
+    pub fn new(threshold: usize, window: Duration, level: LevelFilter, period: Duration) -> Self {
+        Self {
+            watch_level: Level::Warn,
+            threshold,
+            window,
+            level,
+            period,
+            state: Arc::new(Mutex::new(AdaptiveState {
+                hits: VecDeque::new(),
+                restore: None,
+                until: SystemTime::now(),
+            })),
+        }
+    }
+
+    /// records at this level or more severe are counted
+    pub fn watch_level(mut self, level: Level) -> Self {
+        self.watch_level = level;
+        self
+    }
+
+    /// is the level changed now
+    pub fn is_active(&self) -> bool {
+        self.state.lock().restore.is_some()
+    }
+
+    /// count the records of the batch, push an announcement record into records when the level is changed
+    pub fn do_watch(&self, records: &mut Vec<FastLogRecord>) {
+        let mut state = self.state.lock();
+        let mut last = None;
+        for x in records.iter() {
+            if x.command == Command::CommandRecord && x.level <= self.watch_level {
+                state.hits.push_back(x.now);
+                last = Some(x.now);
+            }
+        }
+        let now = match last {
+            None => return,
+            Some(v) => v,
+        };
+        while let Some(first) = state.hits.front() {
+            if now.duration_since(*first).unwrap_or_default() > self.window {
+                state.hits.pop_front();
+            } else {
+                break;
+            }
+        }
+        if state.hits.len() <= self.threshold {
+            return;
+        }
+        state.hits.clear();
+        state.until = now + self.period;
+        if state.restore.is_some() {
+            //still in burst, keep the level longer
+            return;
+        }
+        let current = logger().get_level();
+        if current >= self.level {
+            return;
+        }
+        state.restore = Some(current);
+        logger().set_level(self.level);
+        records.push(notice(format!(
+            "[fast_log] WARN/ERROR burst detected, level changed from {} to {} for {:?}",
+            current, self.level, self.period
+        )));
+        let state = self.state.clone();
+        let level = self.level;
+        spawn(move || loop {
+            let until = state.lock().until;
+            let wait = until
+                .duration_since(SystemTime::now())
+                .unwrap_or_default();
+            if !wait.is_zero() {
+                std::thread::sleep(wait);
+                continue;
+            }
+            let mut state = state.lock();
+            if state.until > SystemTime::now() {
+                continue;
+            }
+            if let Some(restore) = state.restore.take() {
+                //the level is changed by others while the burst, keep it
+                if !logger().levels.compare_set_level(level, restore) {
+                    break;
+                }
+                if let Some(send) = logger().send.get() {
+                    let _ = send.send(notice(format!(
+                        "[fast_log] WARN/ERROR burst is over, level restored to {}",
+                        restore
+                    )));
+                }
+            }
+            break;
+        });
+    }
+}
+
+fn notice(args: String) -> FastLogRecord {
+    FastLogRecord {
+        command: Command::CommandRecord,
+        level: Level::Warn,
+        target: "fast_log".to_string(),
+        args,
+        module_path: module_path!().to_string(),
+        file: file!().to_string(),
+        line: Some(line!()),
+        now: SystemTime::now(),
+        formated: String::new(),
+    }
+}
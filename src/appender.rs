@@ -6,14 +6,27 @@ use std::time::SystemTime;
 pub trait LogAppender:Send {
     /// Batch write log, or do nothing
     fn do_logs(&mut self, records: &[FastLogRecord]);
+
+    /// called by the appender thread when no record arrives within `Config::tick`,
+    /// for example do time-based rolling when there is no traffic
+    fn do_tick(&mut self, _now: SystemTime) {}
+
+    /// reopen the log files by path, for example after logrotate moved them. see `fast_log::reopen`
+    fn reopen(&mut self) {}
 }
 
+/// the command of an record, more commands may be added, match it with an `_` arm
 #[derive(Clone, Debug)]
+#[non_exhaustive]
 pub enum Command {
     CommandRecord,
     CommandExit,
     /// Ensure that the log splitter forces splitting and saves the log
     CommandFlush(WaitGroup),
+    /// force the rolling appenders to roll and pack the current log file now
+    CommandRoll(WaitGroup),
+    /// write and sync(fsync) the log files, used by the records of `Config::sync_level`
+    CommandSync(WaitGroup),
 }
 
 impl Command {
@@ -22,6 +35,8 @@ impl Command {
             Command::CommandRecord => 1,
             Command::CommandExit => 2,
             Command::CommandFlush(_) => 3,
+            Command::CommandRoll(_) => 4,
+            Command::CommandSync(_) => 5,
         }
     }
 }
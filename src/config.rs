@@ -1,9 +1,14 @@
+use crate::adaptive::AdaptiveLevel;
 use crate::appender::{LogAppender, RecordFormat};
 use crate::consts::LogSize;
+use crate::error::{ErrorHandler, StderrErrorHandler};
 use crate::filter::Filter;
+use crate::fingers_crossed::FingersCrossed;
 use crate::plugin::console::{ConsoleAppender, ConsoleStderrAppender};
+use crate::plugin::durability::Durability;
 use crate::plugin::file::FileAppender;
 use crate::plugin::file_loop::FileLoopAppender;
+use crate::plugin::pack_pool::PackPool;
 use crate::plugin::file_split::{
     CanRollingPack, FileSplitAppender, Keep, Packer, RawFile, SplitFile,
 };
@@ -12,6 +17,7 @@ use dark_std::sync::SyncVec;
 use log::LevelFilter;
 use parking_lot::Mutex;
 use std::fmt::{Debug, Formatter};
+use std::time::Duration;
 
 /// the fast_log Config
 /// for example:
@@ -35,6 +41,22 @@ pub struct Config {
     pub chan_len: Option<usize>,
     /// number of worker threads that receive and dispatch log records from the main channel to appenders
     pub worker_tasks: Option<usize>,
+    /// buffer verbose records and only write them when an error record arrives,default None
+    pub fingers_crossed: Option<FingersCrossed>,
+    /// change the level automatically on WARN/ERROR bursts,default None
+    pub adaptive_level: Option<AdaptiveLevel>,
+    /// level overrides by target prefix,for example ("my_crate::db", LevelFilter::Trace)
+    pub target_levels: Vec<(String, LevelFilter)>,
+    /// call LogAppender::do_tick when no record arrives within the duration,default 1s
+    pub tick: Option<Duration>,
+    /// the shared pack workers of FileSplitAppenders added after it,default None(one saver thread per appender)
+    pub pack_pool: Option<PackPool>,
+    /// receive the errors of appenders, packers and keepers,default StderrErrorHandler
+    pub error_handler: Box<dyn ErrorHandler>,
+    /// the Durability of file appenders added after it(`file`,`file_split`,`split`),default Durability::Never
+    pub durability: Durability,
+    /// the records of this level(or more severe) are written and synced before `log!` returns,default Off
+    pub sync_level: LevelFilter,
 }
 
 impl Debug for Config {
@@ -56,6 +78,14 @@ impl Default for Config {
             format: Box::new(FastLogFormat::new()),
             chan_len: None,
             worker_tasks: Some(1),
+            fingers_crossed: None,
+            adaptive_level: None,
+            target_levels: vec![],
+            tick: Some(Duration::from_secs(1)),
+            pack_pool: None,
+            error_handler: Box::new(StderrErrorHandler::default()),
+            durability: Durability::Never,
+            sync_level: LevelFilter::Off,
         }
     }
 }
@@ -70,6 +100,11 @@ impl Config {
         self.level = level;
         self
     }
+    /// set the LevelFilter of targets start with `target`
+    pub fn target_level(mut self, target: &str, level: LevelFilter) -> Self {
+        self.target_levels.push((target.to_string(), level));
+        self
+    }
     /// add log Filter
     pub fn add_filter<F: Filter + 'static>(self, filter: F) -> Self {
         self.filters.push(Box::new(filter));
@@ -100,8 +135,8 @@ impl Config {
     }
     /// add a FileAppender
     pub fn file(self, file: &str) -> Self {
-        self.appends
-            .push(Mutex::new(Box::new(FileAppender::new(file).unwrap())));
+        let appender = FileAppender::new(file).unwrap().durability(self.durability);
+        self.appends.push(Mutex::new(Box::new(appender)));
         self
     }
     /// add a FileLoopAppender
@@ -111,6 +146,27 @@ impl Config {
         )));
         self
     }
+    /// set the ErrorHandler
+    pub fn error_handler<H: ErrorHandler + 'static>(mut self, handler: H) -> Self {
+        self.error_handler = Box::new(handler);
+        self
+    }
+    /// set the Durability of the file appenders added after it(`file`,`file_split`,`split`)
+    pub fn durability(mut self, durability: Durability) -> Self {
+        self.durability = durability;
+        self
+    }
+    /// write and sync the records of level(or more severe) before `log!` returns,
+    /// for example LevelFilter::Warn for the ERROR and WARN records
+    pub fn sync_level(mut self, level: LevelFilter) -> Self {
+        self.sync_level = level;
+        self
+    }
+    /// share the PackPool by the FileSplitAppenders added after it(`file_split`,`split`)
+    pub fn pack_pool(mut self, pool: PackPool) -> Self {
+        self.pack_pool = Some(pool);
+        self
+    }
     /// add a FileSplitAppender
     pub fn file_split<
         R: CanRollingPack + 'static,
@@ -123,15 +179,18 @@ impl Config {
         keeper: K,
         packer: P,
     ) -> Self {
-        self.appends.push(Mutex::new(Box::new(
-            FileSplitAppender::new::<RawFile>(
-                file_path,
-                Box::new(rolling),
-                Box::new(keeper),
-                Box::new(packer),
-            )
-            .expect("new split file fail"),
-        )));
+        let mut appender = FileSplitAppender::new::<RawFile>(
+            file_path,
+            Box::new(rolling),
+            Box::new(keeper),
+            Box::new(packer),
+        )
+        .expect("new split file fail")
+        .durability(self.durability);
+        if let Some(pool) = &self.pack_pool {
+            appender = appender.pack_pool(pool.clone());
+        }
+        self.appends.push(Mutex::new(Box::new(appender)));
         self
     }
 
@@ -168,15 +227,18 @@ impl Config {
         packer: P,
         how_pack: H,
     ) -> Self {
-        self.appends.push(Mutex::new(Box::new(
-            FileSplitAppender::new::<F>(
-                file_path,
-                Box::new(how_pack),
-                Box::new(keeper),
-                Box::new(packer),
-            )
-            .expect("new split file fail"),
-        )));
+        let mut appender = FileSplitAppender::new::<F>(
+            file_path,
+            Box::new(how_pack),
+            Box::new(keeper),
+            Box::new(packer),
+        )
+        .expect("new split file fail")
+        .durability(self.durability);
+        if let Some(pool) = &self.pack_pool {
+            appender = appender.pack_pool(pool.clone());
+        }
+        self.appends.push(Mutex::new(Box::new(appender)));
         self
     }
     /// add a custom LogAppender
@@ -201,4 +263,22 @@ impl Config {
         self.worker_tasks = num;
         self
     }
+
+    /// set the tick duration of appenders, None is never tick
+    pub fn tick(mut self, tick: Option<Duration>) -> Self {
+        self.tick = tick;
+        self
+    }
+
+    /// enable fingers crossed buffering
+    pub fn fingers_crossed(mut self, fingers_crossed: FingersCrossed) -> Self {
+        self.fingers_crossed = Some(fingers_crossed);
+        self
+    }
+
+    /// enable adaptive verbosity on WARN/ERROR bursts
+    pub fn adaptive_level(mut self, adaptive_level: AdaptiveLevel) -> Self {
+        self.adaptive_level = Some(adaptive_level);
+        self
+    }
 }
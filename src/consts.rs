@@ -1,6 +1,6 @@
 use crate::error::LogError;
 
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, Debug)]
 pub enum LogSize {
     B(usize),
     KB(usize),
@@ -1,8 +1,13 @@
+use std::collections::HashMap;
 use std::error::Error;
 use std::fmt;
 use std::fmt::Display;
+use std::sync::OnceLock;
+use std::time::{Duration, Instant};
 
+use crate::logger;
 use log::SetLoggerError;
+use parking_lot::Mutex;
 
 #[derive(Clone, Debug)]
 pub enum LogError {
@@ -56,3 +61,120 @@ impl Default for LogError {
         LogError::E(String::new())
     }
 }
+
+/// an error of appender, packer or keeper, reported to `Config::error_handler`
+#[derive(Clone, Debug)]
+pub struct ReportError {
+    /// for example "FileAppender","FileSplitAppender","Packer","Keep"
+    pub component: &'static str,
+    /// for example "write","copy","pack","remove"
+    pub action: &'static str,
+    pub path: String,
+    /// the io error kind, None if it is not an io error
+    pub kind: Option<std::io::ErrorKind>,
+    pub message: String,
+}
+
+impl ReportError {
+    pub fn new(component: &'static str, action: &'static str, path: &str, message: String) -> Self {
+        Self {
+            component,
+            action,
+            path: path.to_string(),
+            kind: None,
+            message,
+        }
+    }
+
+    pub fn io(
+        component: &'static str,
+        action: &'static str,
+        path: &str,
+        e: &std::io::Error,
+    ) -> Self {
+        Self {
+            component,
+            action,
+            path: path.to_string(),
+            kind: Some(e.kind()),
+            message: e.to_string(),
+        }
+    }
+}
+
+impl Display for ReportError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "[fast_log] {} {} fail, path={}, kind={:?}, error={}",
+            self.component, self.action, self.path, self.kind, self.message
+        )
+    }
+}
+
+/// receive the errors of appenders, packers and keepers
+pub trait ErrorHandler: Send + Sync {
+    fn handle(&self, error: &ReportError);
+}
+
+/// (component, action) => (last print time, suppressed num)
+type ReportTimes = HashMap<(&'static str, &'static str), (Instant, usize)>;
+
+/// print errors to stderr, the same component and action print once per `interval`
+pub struct StderrErrorHandler {
+    pub interval: Duration,
+    last: Mutex<ReportTimes>,
+}
+
+impl StderrErrorHandler {
+    pub fn new(interval: Duration) -> Self {
+        Self {
+            interval,
+            last: Mutex::new(HashMap::new()),
+        }
+    }
+}
+
+impl Default for StderrErrorHandler {
+    fn default() -> Self {
+        Self::new(Duration::from_secs(10))
+    }
+}
+
+impl ErrorHandler for StderrErrorHandler {
+    fn handle(&self, error: &ReportError) {
+        let now = Instant::now();
+        let mut last = self.last.lock();
+        let suppressed = match last.get_mut(&(error.component, error.action)) {
+            Some((time, suppressed)) => {
+                if now.duration_since(*time) < self.interval {
+                    *suppressed += 1;
+                    return;
+                }
+                *time = now;
+                std::mem::take(suppressed)
+            }
+            None => {
+                last.insert((error.component, error.action), (now, 0));
+                0
+            }
+        };
+        drop(last);
+        if suppressed > 0 {
+            eprintln!("{} ({} similar errors suppressed)", error, suppressed);
+        } else {
+            eprintln!("{}", error);
+        }
+    }
+}
+
+/// report an error to the `Config::error_handler`, or the default StderrErrorHandler before init
+pub fn report(error: ReportError) {
+    match logger().cfg.get() {
+        Some(cfg) => cfg.error_handler.handle(&error),
+        None => {
+            static DEFAULT: OnceLock<StderrErrorHandler> = OnceLock::new();
+            DEFAULT.get_or_init(StderrErrorHandler::default).handle(&error);
+        }
+    }
+}
@@ -1,11 +1,21 @@
 use crate::appender::{Command, FastLogRecord};
 use crate::config::Config;
 use crate::error::LogError;
-use crate::{chan, spawn, Receiver, SendError, Sender, WaitGroup};
+use crate::level::LevelControl;
+use crate::{chan, spawn, Receiver, RecvTimeoutError, SendError, Sender, WaitGroup};
 use log::{LevelFilter, Log, Metadata, Record};
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
 use std::sync::{Arc, OnceLock};
 use std::time::SystemTime;
 
+thread_local! {
+    /// target => enabled levels bit mask, with the level generation
+    static ENABLED_CACHE: RefCell<(u64, HashMap<String, u8>)> = RefCell::new((u64::MAX, HashMap::new()));
+    /// is the worker or appender thread of fast_log, it must not wait the records written
+    static LOG_THREAD: Cell<bool> = const { Cell::new(false) };
+}
+
 pub static LOGGER: OnceLock<Logger> = OnceLock::new();
 
 /// get Logger,but you must call `fast_log::init`
@@ -17,6 +27,8 @@ pub struct Logger {
     pub cfg: OnceLock<Config>,
     pub send: OnceLock<Sender<FastLogRecord>>,
     pub recv: OnceLock<Receiver<FastLogRecord>>,
+    /// runtime level control,support override level by target prefix
+    pub levels: LevelControl,
 }
 
 impl Logger {
@@ -25,15 +37,16 @@ impl Logger {
             cfg: OnceLock::default(),
             send: OnceLock::default(),
             recv: OnceLock::default(),
+            levels: LevelControl::new_global(LevelFilter::Trace),
         }
     }
 
     pub fn set_level(&self, level: LevelFilter) {
-        log::set_max_level(level);
+        self.levels.set_level(level);
     }
 
     pub fn get_level(&self) -> LevelFilter {
-        log::max_level()
+        self.levels.level()
     }
 
     /// print no other info
@@ -60,13 +73,61 @@ impl Logger {
     pub fn wait(&self) {
         self.flush();
     }
+
+    /// bit mask of the enabled levels of target,by target levels and filters
+    fn enabled_mask(&self, target: &str) -> u8 {
+        let level = self.levels.level_for(target);
+        let mut mask = 0;
+        for x in log::Level::iter() {
+            if x > level {
+                continue;
+            }
+            let metadata = Metadata::builder().level(x).target(target).build();
+            let mut enabled = true;
+            if let Some(cfg) = self.cfg.get() {
+                for filter in cfg.filters.iter() {
+                    if !filter.enabled(&metadata) {
+                        enabled = false;
+                        break;
+                    }
+                }
+            }
+            if enabled {
+                mask |= 1 << x as usize;
+            }
+        }
+        mask
+    }
 }
 
 impl Log for Logger {
     fn enabled(&self, metadata: &Metadata) -> bool {
-        metadata.level() <= self.get_level()
+        let no_filter = self.cfg.get().map(|c| c.filters.is_empty()).unwrap_or(true);
+        if no_filter && !self.levels.has_target_levels() {
+            return metadata.level() <= self.levels.level();
+        }
+        let generation = self.levels.generation();
+        ENABLED_CACHE.with(|cache| {
+            let mut cache = cache.borrow_mut();
+            if cache.0 != generation || cache.1.len() > 1024 {
+                cache.0 = generation;
+                cache.1.clear();
+            }
+            let mask = match cache.1.get(metadata.target()) {
+                Some(v) => *v,
+                None => {
+                    let v = self.enabled_mask(metadata.target());
+                    cache.1.insert(metadata.target().to_string(), v);
+                    v
+                }
+            };
+            mask & (1 << metadata.level() as usize) != 0
+        })
     }
     fn log(&self, record: &Record) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
         if let Some(filter) = logger().cfg.get() {
             if let Some(send) = logger().send.get() {
                 for filter in filter.filters.iter() {
@@ -85,6 +146,12 @@ impl Log for Logger {
                     now: SystemTime::now(),
                     formated: String::new(),
                 });
+                //wait the record written and synced
+                if record.level() <= filter.sync_level && !LOG_THREAD.with(|v| v.get()) {
+                    if let Ok(wg) = sync() {
+                        wg.wait();
+                    }
+                }
             }
         }
     }
@@ -116,9 +183,12 @@ pub fn init(config: Config) -> Result<&'static Logger, LogError> {
         .cfg
         .set(config)
         .map_err(|_| LogError::from("set fail="))?;
+    for (target, level) in logger().cfg.get().expect("logger cfg is none").target_levels.iter() {
+        logger().levels.set_target_level(target, *level, None);
+    }
     //main recv data
     log::set_logger(logger())
-        .map(|()| log::set_max_level(logger().cfg.get().expect("logger cfg is none").level))
+        .map(|()| logger().set_level(logger().cfg.get().expect("logger cfg is none").level))
         .map_err(|e| LogError::from(e))?;
 
     let mut receiver_vec = vec![];
@@ -130,13 +200,29 @@ pub fn init(config: Config) -> Result<&'static Logger, LogError> {
         receiver_vec.push((r, a));
     }
     for (receiver, appender) in receiver_vec {
+        let tick = cfg.tick;
         spawn(move || {
+            LOG_THREAD.with(|v| v.set(true));
             let mut exit = false;
             loop {
                 let mut remain = vec![];
                 if receiver.len() == 0 {
-                    if let Ok(msg) = receiver.recv() {
-                        remain.push(msg);
+                    match tick {
+                        None => {
+                            if let Ok(msg) = receiver.recv() {
+                                remain.push(msg);
+                            }
+                        }
+                        Some(tick) => match receiver.recv_timeout(tick) {
+                            Ok(msg) => {
+                                remain.push(msg);
+                            }
+                            Err(RecvTimeoutError::Timeout) => {
+                                appender.lock().do_tick(SystemTime::now());
+                                continue;
+                            }
+                            Err(RecvTimeoutError::Disconnected) => {}
+                        },
                     }
                 }
                 //recv all
@@ -161,7 +247,9 @@ pub fn init(config: Config) -> Result<&'static Logger, LogError> {
                                 exit = true;
                                 continue;
                             }
-                            Command::CommandFlush(_) => {
+                            Command::CommandFlush(_)
+                            | Command::CommandRoll(_)
+                            | Command::CommandSync(_) => {
                                 continue;
                             }
                         }
@@ -178,6 +266,7 @@ pub fn init(config: Config) -> Result<&'static Logger, LogError> {
     for _ in 0..thread_num {
         let senders = sender_vec.clone();
         spawn(move || {
+            LOG_THREAD.with(|v| v.set(true));
             loop {
                 if let Some(recv) = logger().recv.get() {
                     let mut remain = Vec::with_capacity(recv.len());
@@ -199,19 +288,24 @@ pub fn init(config: Config) -> Result<&'static Logger, LogError> {
                         }
                     }
                     let mut exit = false;
+                    let cfg = logger().cfg.get().expect("logger cfg is none");
+                    if let Some(adaptive_level) = &cfg.adaptive_level {
+                        adaptive_level.do_watch(&mut remain);
+                    }
                     for x in &mut remain {
                         if x.formated.is_empty() {
-                            logger()
-                                .cfg
-                                .get()
-                                .expect("logger cfg is none")
-                                .format
-                                .do_format(x);
+                            cfg.format.do_format(x);
                         }
                         if x.command.eq(&Command::CommandExit) {
                             exit = true;
                         }
                     }
+                    if let Some(fingers_crossed) = &cfg.fingers_crossed {
+                        remain = fingers_crossed.do_filter(remain);
+                        if remain.is_empty() {
+                            continue;
+                        }
+                    }
                     let data = Arc::new(remain);
                     for x in senders.iter() {
                         let _ = x.send(data.clone());
@@ -281,6 +375,85 @@ pub fn flush() -> Result<WaitGroup, LogError> {
     return Err(LogError::E("[fast_log] flush fail!".to_string()));
 }
 
+/// force every rolling appender(FileSplitAppender) to roll and pack the current log file now,
+/// call `wait()` of the result to wait the packs done
+pub fn roll() -> Result<WaitGroup, LogError> {
+    let wg = WaitGroup::new();
+    let fast_log_record = FastLogRecord {
+        command: Command::CommandRoll(wg.clone()),
+        level: log::Level::Info,
+        target: String::new(),
+        args: String::new(),
+        module_path: String::new(),
+        file: String::new(),
+        line: None,
+        now: SystemTime::now(),
+        formated: String::new(),
+    };
+    logger()
+        .send
+        .get()
+        .ok_or_else(|| LogError::from("not init"))?
+        .send(fast_log_record)
+        .map_err(|_| LogError::E("[fast_log] roll fail!".to_string()))?;
+    Ok(wg)
+}
+
+/// write and sync(fsync) the records sent before it to the log files,
+/// call `wait()` of the result to wait the files synced
+pub fn sync() -> Result<WaitGroup, LogError> {
+    let wg = WaitGroup::new();
+    let fast_log_record = FastLogRecord {
+        command: Command::CommandSync(wg.clone()),
+        level: log::Level::Info,
+        target: String::new(),
+        args: String::new(),
+        module_path: String::new(),
+        file: String::new(),
+        line: None,
+        now: SystemTime::now(),
+        formated: String::new(),
+    };
+    logger()
+        .send
+        .get()
+        .ok_or_else(|| LogError::from("not init"))?
+        .send(fast_log_record)
+        .map_err(|_| LogError::E("[fast_log] sync fail!".to_string()))?;
+    Ok(wg)
+}
+
+/// reopen the log files of all appenders by path, for example after logrotate moved them
+pub fn reopen() -> Result<(), LogError> {
+    let cfg = logger()
+        .cfg
+        .get()
+        .ok_or_else(|| LogError::from("not init"))?;
+    for x in cfg.appends.iter() {
+        x.lock().reopen();
+    }
+    Ok(())
+}
+
+/// you need enable fast_log = { ... ,features=["signal"]}
+/// reopen the log files when receive the signals, for example the SIGHUP sent by logrotate `postrotate`
+/// ```rust,ignore
+/// fast_log::reopen_on_signal(&[fast_log::SIGHUP, fast_log::SIGUSR1]).unwrap();
+/// ```
+#[cfg(all(unix, feature = "signal"))]
+pub fn reopen_on_signal(signals: &[i32]) -> Result<(), LogError> {
+    let mut signals = signal_hook::iterator::Signals::new(signals)?;
+    spawn(move || {
+        for _ in signals.forever() {
+            let _ = reopen();
+        }
+    });
+    Ok(())
+}
+
+#[cfg(all(unix, feature = "signal"))]
+pub use signal_hook::consts::{SIGHUP, SIGUSR1};
+
 pub fn print(log: String) -> Result<(), SendError<FastLogRecord>> {
     logger().print(log)
 }
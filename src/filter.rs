@@ -4,6 +4,15 @@ use dark_std::sync::SyncVec;
 pub trait Filter: Send + Sync {
     /// if return true=do_log/false=not_log
     fn do_log(&self, record: &log::Record) -> bool;
+
+    /// decide from metadata only, used by `log_enabled!` to skip building the record.
+    /// return false only if every record of this metadata will be filtered,default true.
+    /// the result is cached by target until the level changed.
+    /// notice: the metadata has no module path, so the filters by module(for example ModuleFilter)
+    /// can not decide here, `log_enabled!` is true for the records they drop. use `Config::target_level` for it
+    fn enabled(&self, _metadata: &log::Metadata) -> bool {
+        true
+    }
 }
 
 /// an Module Filter
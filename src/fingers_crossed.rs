@@ -0,0 +1,96 @@
+use crate::appender::{Command, FastLogRecord};
+use log::{Level, LevelFilter};
+use parking_lot::Mutex;
+use std::collections::{HashMap, VecDeque};
+
+/// fingers crossed buffering.
+/// the verbose records(DEBUG/TRACE by default) are kept in a bounded ring and discarded normally,
+/// when an ERROR record arrives the buffered records preceding it are sent to the appenders first.
+/// for example:
+/// ```rust
+/// use fast_log::Config;
+/// use fast_log::fingers_crossed::FingersCrossed;
+/// fn main(){
+///    fast_log::init(Config::new().console().fingers_crossed(FingersCrossed::new(1000))).unwrap();
+/// }
+/// ```
+pub struct FingersCrossed {
+    /// records at this level or more verbose are buffered
+    pub buffer_level: LevelFilter,
+    /// records at this level or more severe dump the buffered records
+    pub trigger_level: Level,
+    /// max records of one buffer
+    pub capacity: usize,
+    /// true = one buffer per target, an trigger only dump the buffer of its target
+    pub per_target: bool,
+    buffers: Mutex<HashMap<String, VecDeque<FastLogRecord>>>,
+}
+
+impl FingersCrossed {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            buffer_level: LevelFilter::Debug,
+            trigger_level: Level::Error,
+            capacity,
+            per_target: false,
+            buffers: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// records at this level or more verbose are buffered, default Debug
+    pub fn buffer_level(mut self, level: LevelFilter) -> Self {
+        self.buffer_level = level;
+        self
+    }
+
+    /// records at this level or more severe dump the buffer, default Error
+    pub fn trigger_level(mut self, level: Level) -> Self {
+        self.trigger_level = level;
+        self
+    }
+
+    /// keep one buffer per target, default false
+    pub fn per_target(mut self, per_target: bool) -> Self {
+        self.per_target = per_target;
+        self
+    }
+
+    /// buffer the verbose records of the batch, return the records should send to appenders
+    pub fn do_filter(&self, records: Vec<FastLogRecord>) -> Vec<FastLogRecord> {
+        let mut result = Vec::with_capacity(records.len());
+        let mut buffers = self.buffers.lock();
+        for x in records {
+            if x.command != Command::CommandRecord {
+                result.push(x);
+                continue;
+            }
+            let key = if self.per_target { x.target.as_str() } else { "" };
+            if x.level <= self.trigger_level {
+                if let Some(buffer) = buffers.get_mut(key) {
+                    result.extend(buffer.drain(..));
+                }
+                result.push(x);
+            } else if x.level.to_level_filter() >= self.buffer_level {
+                if self.capacity == 0 {
+                    continue;
+                }
+                let buffer = match buffers.get_mut(key) {
+                    Some(v) => v,
+                    None => buffers.entry(key.to_string()).or_default(),
+                };
+                if buffer.len() >= self.capacity {
+                    buffer.pop_front();
+                }
+                buffer.push_back(x);
+            } else {
+                result.push(x);
+            }
+        }
+        result
+    }
+
+    /// the number of buffered records
+    pub fn buffered(&self) -> usize {
+        self.buffers.lock().values().map(|x| x.len()).sum()
+    }
+}
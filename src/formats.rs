@@ -1,6 +1,7 @@
 use crate::appender::{Command, FastLogRecord, RecordFormat};
 use log::LevelFilter;
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum TimeType {
     Local,
     //default
@@ -44,6 +45,8 @@ impl RecordFormat for FastLogFormat {
             }
             Command::CommandExit => {}
             Command::CommandFlush(_) => {}
+            Command::CommandRoll(_) => {}
+            Command::CommandSync(_) => {}
         }
     }
 }
@@ -107,6 +110,8 @@ impl RecordFormat for FastLogFormatJson {
             }
             Command::CommandExit => {}
             Command::CommandFlush(_) => {}
+            Command::CommandRoll(_) => {}
+            Command::CommandSync(_) => {}
         }
     }
 }
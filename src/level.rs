@@ -0,0 +1,235 @@
+use log::LevelFilter;
+use parking_lot::RwLock;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+
+/// an level override for all targets start with `target`
+#[derive(Clone, Debug)]
+pub struct LevelDirective {
+    /// target prefix, for example `my_crate::db`
+    pub target: String,
+    pub level: LevelFilter,
+    /// the time of the override revert, None is never
+    pub expire: Option<SystemTime>,
+}
+
+/// runtime level control handle.
+/// the base level works like `log::max_level`, the directives override the level by target prefix(module path boundary).
+/// only the control of the logger(`fast_log::logger().levels`) updates `log::max_level`.
+/// for example:
+/// ```rust
+/// use std::time::Duration;
+/// use log::LevelFilter;
+/// // turn on trace for `my_crate::db` five minutes,then revert
+/// fast_log::logger().levels.set_target_level("my_crate::db", LevelFilter::Trace, Some(Duration::from_secs(300)));
+/// ```
+#[derive(Clone)]
+pub struct LevelControl {
+    inner: Arc<LevelInner>,
+}
+
+struct LevelInner {
+    level: AtomicUsize,
+    directives: RwLock<Vec<LevelDirective>>,
+    directives_len: AtomicUsize,
+    /// the unix millis of the nearest expire, u64::MAX is none
+    next_expire: AtomicU64,
+    generation: AtomicU64,
+    /// update `log::max_level`
+    global: bool,
+}
+
+impl Default for LevelControl {
+    fn default() -> Self {
+        Self::new(LevelFilter::Trace)
+    }
+}
+
+impl LevelControl {
+    pub fn new(level: LevelFilter) -> Self {
+        Self::with_global(level, false)
+    }
+
+    /// the control of the logger, it updates `log::max_level`
+    pub(crate) fn new_global(level: LevelFilter) -> Self {
+        Self::with_global(level, true)
+    }
+
+    fn with_global(level: LevelFilter, global: bool) -> Self {
+        Self {
+            inner: Arc::new(LevelInner {
+                level: AtomicUsize::new(level as usize),
+                directives: RwLock::new(vec![]),
+                directives_len: AtomicUsize::new(0),
+                next_expire: AtomicU64::new(u64::MAX),
+                generation: AtomicU64::new(0),
+                global,
+            }),
+        }
+    }
+
+    /// set the base level
+    pub fn set_level(&self, level: LevelFilter) {
+        self.inner.level.store(level as usize, Ordering::SeqCst);
+        self.update_max_level();
+    }
+
+    /// set the base level only if it is still `current`, return is it set
+    pub fn compare_set_level(&self, current: LevelFilter, level: LevelFilter) -> bool {
+        let set = self
+            .inner
+            .level
+            .compare_exchange(
+                current as usize,
+                level as usize,
+                Ordering::SeqCst,
+                Ordering::SeqCst,
+            )
+            .is_ok();
+        if set {
+            self.update_max_level();
+        }
+        set
+    }
+
+    /// get the base level
+    pub fn level(&self) -> LevelFilter {
+        to_level_filter(self.inner.level.load(Ordering::Relaxed))
+    }
+
+    /// override the level of targets start with `target`.
+    /// if duration is Some, the override will revert when the duration elapsed(checked when the level is read).
+    pub fn set_target_level(&self, target: &str, level: LevelFilter, duration: Option<Duration>) {
+        {
+            let mut directives = self.inner.directives.write();
+            directives.retain(|x| x.target != target);
+            directives.push(LevelDirective {
+                target: target.to_string(),
+                level,
+                expire: duration.map(|d| SystemTime::now() + d),
+            });
+            //longest prefix first
+            directives.sort_by_key(|x| std::cmp::Reverse(x.target.len()));
+            self.inner
+                .directives_len
+                .store(directives.len(), Ordering::SeqCst);
+        }
+        self.update_max_level();
+    }
+
+    /// remove the override of `target`,return is removed
+    pub fn remove_target_level(&self, target: &str) -> bool {
+        let removed = {
+            let mut directives = self.inner.directives.write();
+            let len = directives.len();
+            directives.retain(|x| x.target != target);
+            self.inner
+                .directives_len
+                .store(directives.len(), Ordering::SeqCst);
+            len != directives.len()
+        };
+        self.update_max_level();
+        removed
+    }
+
+    /// remove all overrides
+    pub fn clear_target_levels(&self) {
+        {
+            let mut directives = self.inner.directives.write();
+            directives.clear();
+            self.inner.directives_len.store(0, Ordering::SeqCst);
+        }
+        self.update_max_level();
+    }
+
+    /// the active overrides
+    pub fn target_levels(&self) -> Vec<LevelDirective> {
+        self.inner.directives.read().clone()
+    }
+
+    /// is there any override
+    pub fn has_target_levels(&self) -> bool {
+        self.inner.directives_len.load(Ordering::Relaxed) != 0
+    }
+
+    /// changed on every level change, used to invalidate the cached levels
+    pub fn generation(&self) -> u64 {
+        self.remove_expired();
+        self.inner.generation.load(Ordering::Acquire)
+    }
+
+    /// the effective level of target
+    pub fn level_for(&self, target: &str) -> LevelFilter {
+        if self.inner.directives_len.load(Ordering::Relaxed) != 0 {
+            self.remove_expired();
+            for x in self.inner.directives.read().iter() {
+                if is_target_of(target, &x.target) {
+                    return x.level;
+                }
+            }
+        }
+        self.level()
+    }
+
+    /// remove the expired overrides if the nearest expire is reached
+    fn remove_expired(&self) {
+        let next = self.inner.next_expire.load(Ordering::Relaxed);
+        if next == u64::MAX || unix_millis(SystemTime::now()) < next {
+            return;
+        }
+        {
+            let now = SystemTime::now();
+            let mut directives = self.inner.directives.write();
+            directives.retain(|x| x.expire.map(|e| e > now).unwrap_or(true));
+            self.inner
+                .directives_len
+                .store(directives.len(), Ordering::SeqCst);
+        }
+        self.update_max_level();
+    }
+
+    /// log::max_level must allow the most verbose override,or the record never reach the logger
+    fn update_max_level(&self) {
+        let mut max = self.level();
+        let mut next_expire = u64::MAX;
+        for x in self.inner.directives.read().iter() {
+            if x.level > max {
+                max = x.level;
+            }
+            if let Some(expire) = x.expire {
+                next_expire = next_expire.min(unix_millis(expire));
+            }
+        }
+        self.inner.next_expire.store(next_expire, Ordering::SeqCst);
+        if self.inner.global {
+            log::set_max_level(max);
+        }
+        self.inner.generation.fetch_add(1, Ordering::Release);
+    }
+}
+
+/// is target equal to the module path `prefix` or in it, for example `my_crate::db` is in `my_crate`
+fn is_target_of(target: &str, prefix: &str) -> bool {
+    match target.strip_prefix(prefix) {
+        Some(rest) => rest.is_empty() || rest.starts_with("::"),
+        None => false,
+    }
+}
+
+fn unix_millis(time: SystemTime) -> u64 {
+    time.duration_since(SystemTime::UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+fn to_level_filter(v: usize) -> LevelFilter {
+    match v {
+        0 => LevelFilter::Off,
+        1 => LevelFilter::Error,
+        2 => LevelFilter::Warn,
+        3 => LevelFilter::Info,
+        4 => LevelFilter::Debug,
+        _ => LevelFilter::Trace,
+    }
+}
@@ -1,4 +1,6 @@
-#![forbid(unsafe_code)]
+#![cfg_attr(not(feature = "mmap"), forbid(unsafe_code))]
+#![cfg_attr(feature = "mmap", deny(unsafe_code))]
+pub mod adaptive;
 pub mod appender;
 pub mod bencher;
 pub mod config;
@@ -6,7 +8,9 @@ pub mod consts;
 pub mod error;
 pub mod fast_log;
 pub mod filter;
+pub mod fingers_crossed;
 pub mod formats;
+pub mod level;
 pub mod plugin;
 pub mod runtime;
 
@@ -0,0 +1,178 @@
+use crate::error::{report, LogError, ReportError};
+use crate::plugin::file_split::SplitFile;
+use std::cell::RefCell;
+use std::fs::{File, OpenOptions};
+use std::io::{SeekFrom, Write};
+use std::marker::PhantomData;
+
+/// the buffered bytes are compressed into one frame when reach it
+pub const FRAME_SIZE: usize = 64 * 1024;
+
+/// compress data into one complete frame(gzip member, zstd frame, lz4 frame),
+/// the frames can be concatenated and decoded by the standard tools
+pub trait FrameCompress: Send {
+    fn compress(data: &[u8]) -> std::io::Result<Vec<u8>>;
+}
+
+/// an SplitFile compress records while writing, the uncompressed data never hits disk.
+/// the records are buffered in memory and written as an complete frame when the buffer reach `FRAME_SIZE`,
+/// or when flush(`log::logger().flush()`, the tick of appender, and before rolling),
+/// so the file is always decodable after an crash, only the records not flushed are lost.
+/// the size of BySize rolling is the uncompressed size. use an suffix of the compress,for example:
+/// ```rust,ignore
+/// use fast_log::Config;
+/// use fast_log::consts::LogSize;
+/// use fast_log::plugin::compress_file::GZipFile;
+/// use fast_log::plugin::file_split::{KeepType, Rolling, RollingType};
+/// use fast_log::plugin::packer::LogPacker;
+/// let config = Config::new().split::<GZipFile, _, _, _>(
+///     "target/logs/temp.log.gz",
+///     KeepType::KeepNum(10),
+///     LogPacker {},
+///     Rolling::new(RollingType::BySize(LogSize::MB(100))),
+/// );
+/// ```
+pub struct CompressFile<C: FrameCompress> {
+    pub inner: RefCell<File>,
+    path: String,
+    buf: RefCell<Vec<u8>>,
+    c: PhantomData<C>,
+}
+
+impl<C: FrameCompress> CompressFile<C> {
+    /// write the buffered data as an frame
+    fn write_frame(&self) -> std::io::Result<()> {
+        let mut buf = self.buf.borrow_mut();
+        if buf.is_empty() {
+            return Ok(());
+        }
+        let frame = C::compress(&buf)?;
+        buf.clear();
+        self.inner.borrow_mut().write_all(&frame)
+    }
+}
+
+impl<C: FrameCompress> SplitFile for CompressFile<C> {
+    fn new(path: &str) -> Result<Self, LogError>
+    where
+        Self: Sized,
+    {
+        //the new frames are appended after the frames of last process
+        let file = OpenOptions::new()
+            .create(true)
+            .read(true)
+            .append(true)
+            .open(path)?;
+        Ok(Self {
+            inner: RefCell::new(file),
+            path: path.to_string(),
+            buf: RefCell::new(Vec::with_capacity(FRAME_SIZE)),
+            c: PhantomData,
+        })
+    }
+
+    fn seek(&self, _pos: SeekFrom) -> std::io::Result<u64> {
+        //always append
+        Ok(self.len() as u64)
+    }
+
+    fn write(&self, buf: &[u8]) -> std::io::Result<usize> {
+        self.buf.borrow_mut().extend_from_slice(buf);
+        if self.buf.borrow().len() >= FRAME_SIZE {
+            self.write_frame()?;
+        }
+        Ok(buf.len())
+    }
+
+    fn truncate(&self) -> std::io::Result<()> {
+        self.buf.borrow_mut().clear();
+        self.inner.borrow_mut().set_len(0)?;
+        Ok(())
+    }
+
+    fn flush(&self) {
+        if let Err(e) = self.write_frame() {
+            report(ReportError::io("CompressFile", "write", &self.path, &e));
+        }
+        let _ = self.inner.borrow_mut().flush();
+    }
+
+    fn len(&self) -> usize {
+        match self.inner.borrow().metadata() {
+            Ok(v) => v.len() as usize,
+            Err(_) => 0,
+        }
+    }
+
+    fn offset(&self) -> usize {
+        self.len().saturating_sub(1)
+    }
+
+    fn plain_len(&self) -> usize {
+        //the plain size of the existing frames is unknown, len() is the compressed size
+        0
+    }
+
+    fn sync(&self) -> std::io::Result<()> {
+        self.write_frame()?;
+        self.inner.borrow().sync_data()
+    }
+}
+
+impl<C: FrameCompress> Drop for CompressFile<C> {
+    fn drop(&mut self) {
+        let _ = self.write_frame();
+    }
+}
+
+/// you need enable fast_log = { ... ,features=["gzip"]}
+#[cfg(feature = "gzip")]
+pub struct GZip {}
+
+#[cfg(feature = "gzip")]
+impl FrameCompress for GZip {
+    fn compress(data: &[u8]) -> std::io::Result<Vec<u8>> {
+        let mut encoder =
+            flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+        encoder.write_all(data)?;
+        encoder.finish()
+    }
+}
+
+/// write the active file as gzip members
+#[cfg(feature = "gzip")]
+pub type GZipFile = CompressFile<GZip>;
+
+/// you need enable fast_log = { ... ,features=["zstd"]}
+#[cfg(feature = "zstd")]
+pub struct Zstd {}
+
+#[cfg(feature = "zstd")]
+impl FrameCompress for Zstd {
+    fn compress(data: &[u8]) -> std::io::Result<Vec<u8>> {
+        zstd::bulk::compress(data, 0)
+    }
+}
+
+/// write the active file as zstd frames
+#[cfg(feature = "zstd")]
+pub type ZstdFile = CompressFile<Zstd>;
+
+/// you need enable fast_log = { ... ,features=["lz4"]}
+#[cfg(feature = "lz4")]
+pub struct LZ4 {}
+
+#[cfg(feature = "lz4")]
+impl FrameCompress for LZ4 {
+    fn compress(data: &[u8]) -> std::io::Result<Vec<u8>> {
+        let mut encoder = lz4_flex::frame::FrameEncoder::new(Vec::new());
+        encoder.write_all(data)?;
+        encoder
+            .finish()
+            .map_err(std::io::Error::other)
+    }
+}
+
+/// write the active file as lz4 frames
+#[cfg(feature = "lz4")]
+pub type LZ4File = CompressFile<LZ4>;
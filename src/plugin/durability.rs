@@ -0,0 +1,87 @@
+use crate::appender::{Command, FastLogRecord};
+use std::time::{Duration, Instant};
+
+/// when the file appenders call `sync_data`(fsync), `flush` only write the userspace buffers.
+/// for example:
+/// ```rust
+/// use fast_log::Config;
+/// use fast_log::plugin::durability::Durability;
+/// use std::time::Duration;
+/// let config = Config::new()
+///     .durability(Durability::Interval(Duration::from_secs(1)))
+///     .file("target/test.log");
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Default)]
+pub enum Durability {
+    /// never sync, the os write back the page cache
+    #[default]
+    Never,
+    /// sync after every N records written
+    EveryRecords(usize),
+    /// sync when the duration passed since the last sync(checked when writing and on tick)
+    Interval(Duration),
+    /// sync after writing the records contains an ERROR record
+    OnError,
+}
+
+/// the sync state of an file appender
+#[derive(Clone, Debug)]
+pub struct SyncState {
+    pub durability: Durability,
+    /// the records written since the last sync
+    records: usize,
+    last: Instant,
+}
+
+impl Default for SyncState {
+    fn default() -> Self {
+        Self::new(Durability::Never)
+    }
+}
+
+impl SyncState {
+    pub fn new(durability: Durability) -> Self {
+        Self {
+            durability,
+            records: 0,
+            last: Instant::now(),
+        }
+    }
+
+    /// is need sync after writing the records, `Command::CommandSync` always need
+    pub fn need_sync(&mut self, records: &[FastLogRecord]) -> bool {
+        let mut force = false;
+        let mut error = false;
+        for x in records {
+            match x.command {
+                Command::CommandRecord => {
+                    self.records += 1;
+                    error |= x.level == log::Level::Error;
+                }
+                Command::CommandSync(_) => force = true,
+                _ => {}
+            }
+        }
+        force
+            || match self.durability {
+                Durability::Never => false,
+                Durability::EveryRecords(n) => self.records >= n.max(1),
+                Durability::Interval(_) => self.need_sync_tick(),
+                Durability::OnError => error,
+            }
+    }
+
+    /// is need sync on the tick of appender
+    pub fn need_sync_tick(&self) -> bool {
+        match self.durability {
+            Durability::Interval(d) => self.records > 0 && self.last.elapsed() >= d,
+            _ => false,
+        }
+    }
+
+    /// reset after synced
+    pub fn synced(&mut self) {
+        self.records = 0;
+        self.last = Instant::now();
+    }
+}
@@ -1,12 +1,42 @@
 use crate::appender::{Command, FastLogRecord, LogAppender};
-use crate::error::LogError;
+use crate::error::{report, LogError, ReportError};
+use crate::plugin::durability::{Durability, SyncState};
 use std::cell::RefCell;
 use std::fs::{File, OpenOptions};
 use std::io::Write;
+use std::time::{Duration, SystemTime};
+
+/// the interval of checking the identity of file while the records keep arriving(no tick)
+pub(crate) const CHECK_INTERVAL: Duration = Duration::from_secs(1);
 
 /// only write append into file
 pub struct FileAppender {
     file: RefCell<File>,
+    path: String,
+    sync: SyncState,
+    id: Option<FileId>,
+    /// the time of last check_file
+    checked: SystemTime,
+}
+
+/// the identity of an file(device and inode on unix)
+pub type FileId = (u64, u64);
+
+/// the identity of the file at path, None if it not exists.
+/// it changes when the file is moved or replaced by an external rotation(for example logrotate)
+pub fn file_id(path: &str) -> Option<FileId> {
+    let meta = std::fs::metadata(path).ok()?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::MetadataExt;
+        Some((meta.dev(), meta.ino()))
+    }
+    #[cfg(not(unix))]
+    {
+        //only detect the removed file
+        let _ = meta;
+        Some((0, 0))
+    }
 }
 
 impl FileAppender {
@@ -23,12 +53,43 @@ impl FileAppender {
                     .append(true)
                     .open(&log_file_path)?,
             ),
+            id: file_id(&log_file_path),
+            path: log_file_path,
+            sync: SyncState::default(),
+            checked: SystemTime::now(),
         })
     }
+
+    /// set when sync the file to disk, default Durability::Never
+    pub fn durability(mut self, durability: Durability) -> Self {
+        self.sync = SyncState::new(durability);
+        self
+    }
+
+    fn sync_data(&mut self) {
+        if let Err(e) = self.file.borrow().sync_data() {
+            report(ReportError::io("FileAppender", "sync", &self.path, &e));
+        }
+        self.sync.synced();
+    }
+
+    /// reopen the file if it is moved or removed
+    fn check_file(&mut self, now: SystemTime) {
+        self.checked = now;
+        if file_id(&self.path) != self.id {
+            self.reopen();
+        }
+    }
 }
 
 impl LogAppender for FileAppender {
     fn do_logs(&mut self, records: &[FastLogRecord]) {
+        //the tick is not called on an busy logger
+        if let Some(x) = records.last() {
+            if x.now.duration_since(self.checked).unwrap_or_default() >= CHECK_INTERVAL {
+                self.check_file(x.now);
+            }
+        }
         let mut log_file = self.file.borrow_mut();
         let cap = records.iter().map(|record| record.formated.len()).sum();
         let mut buf = String::with_capacity(cap);
@@ -37,13 +98,41 @@ impl LogAppender for FileAppender {
             match &x.command {
                 Command::CommandRecord => {}
                 Command::CommandExit => {}
-                Command::CommandFlush(_) => {
-                    let _ = log_file.write_all(buf.as_bytes());
-                    let _ = log_file.flush();
+                //not an rolling appender, just flush
+                Command::CommandFlush(_) | Command::CommandRoll(_) | Command::CommandSync(_) => {
+                    if let Err(e) = log_file.write_all(buf.as_bytes()) {
+                        report(ReportError::io("FileAppender", "write", &self.path, &e));
+                    }
+                    if let Err(e) = log_file.flush() {
+                        report(ReportError::io("FileAppender", "flush", &self.path, &e));
+                    }
                     buf.clear();
                 }
             }
         }
-        let _ = log_file.write_all(buf.as_bytes());
+        if let Err(e) = log_file.write_all(buf.as_bytes()) {
+            report(ReportError::io("FileAppender", "write", &self.path, &e));
+        }
+        drop(log_file);
+        if self.sync.need_sync(records) {
+            self.sync_data();
+        }
+    }
+
+    fn do_tick(&mut self, now: SystemTime) {
+        if self.sync.need_sync_tick() {
+            self.sync_data();
+        }
+        self.check_file(now);
+    }
+
+    fn reopen(&mut self) {
+        match OpenOptions::new().create(true).append(true).open(&self.path) {
+            Ok(file) => {
+                *self.file.get_mut() = file;
+                self.id = file_id(&self.path);
+            }
+            Err(e) => report(ReportError::io("FileAppender", "reopen", &self.path, &e)),
+        }
     }
 }
@@ -3,6 +3,7 @@ use crate::consts::LogSize;
 use crate::error::LogError;
 use crate::plugin::file_split::{FileSplitAppender, RollingType, KeepType, RawFile, Rolling};
 use crate::plugin::packer::LogPacker;
+use std::time::SystemTime;
 
 /// Single logs are stored in rolling mode by capacity
 pub struct FileLoopAppender {
@@ -26,4 +27,12 @@ impl LogAppender for FileLoopAppender {
     fn do_logs(&mut self, records: &[FastLogRecord]) {
         self.file.do_logs(records);
     }
+
+    fn do_tick(&mut self, now: SystemTime) {
+        self.file.do_tick(now);
+    }
+
+    fn reopen(&mut self) {
+        self.file.reopen();
+    }
 }
@@ -1,3 +1,5 @@
+use crate::TimeType;
+
 pub trait FileName {
     fn extract_file_name(&self) -> String;
 }
@@ -15,3 +17,388 @@ impl FileName for &str {
         }
     }
 }
+
+/// the default date format of archive name
+pub const DEFAULT_DATE_FORMAT: &str = "YYYY-MM-DDThh-mm-ss.000000";
+
+/// archive file name template.
+/// tokens:
+/// * `{base}` the temp file name without suffix, for example `temp`
+/// * `{suffix}` the suffix of temp file name, for example `.log`
+/// * `{date}` the roll time formatted by `date_format`
+/// * `{host}` the host name
+/// * `{pid}` the process id
+/// * `{seq}` the sequence of rolls, start with 0
+///
+/// default is `{base}{date}{suffix}`, for example `temp2026-10-18T12-00-00.000000.log`.
+/// when an archive of the name exists, the appender inserts `-1`,`-2`... before the suffix.
+#[derive(Clone, Debug)]
+pub struct NameTemplate {
+    pub pattern: String,
+    pub date_format: String,
+    seq: u64,
+}
+
+impl Default for NameTemplate {
+    fn default() -> Self {
+        Self::new("{base}{date}{suffix}")
+    }
+}
+
+impl NameTemplate {
+    pub fn new(pattern: &str) -> Self {
+        Self {
+            pattern: pattern.to_string(),
+            date_format: DEFAULT_DATE_FORMAT.to_string(),
+            seq: 0,
+        }
+    }
+
+    /// set the format of `{date}`, see `fastdate::DateTime::format`
+    pub fn date_format(mut self, format: &str) -> Self {
+        self.date_format = format.to_string();
+        self
+    }
+
+    /// render an archive name of temp_name
+    pub fn render(&mut self, temp_name: &str, time: &fastdate::DateTime) -> String {
+        let (base, suffix) = split_suffix(temp_name);
+        let mut name = self.pattern.replace("{base}", base).replace("{suffix}", suffix);
+        if name.contains("{date}") {
+            name = name.replace("{date}", &time.format(&self.date_format));
+        }
+        if name.contains("{host}") {
+            name = name.replace("{host}", &host_name());
+        }
+        if name.contains("{pid}") {
+            name = name.replace("{pid}", &std::process::id().to_string());
+        }
+        if name.contains("{seq}") {
+            name = name.replace("{seq}", &self.seq.to_string());
+        }
+        self.seq += 1;
+        name
+    }
+}
+
+/// split file name to (base, suffix), for example temp.log => (temp, .log)
+pub fn split_suffix(file_name: &str) -> (&str, &str) {
+    match file_name.rfind('.') {
+        Some(idx) if idx > 0 => (&file_name[..idx], &file_name[idx..]),
+        _ => (file_name, ""),
+    }
+}
+
+/// insert value before the suffix of file path, for example (temp.log, -1) => temp-1.log
+pub fn insert_before_suffix(path: &str, value: &str) -> String {
+    let name_start = path.replace("\\", "/").rfind('/').map(|x| x + 1).unwrap_or(0);
+    let (base, suffix) = split_suffix(&path[name_start..]);
+    format!("{}{}{}{}", &path[..name_start], base, value, suffix)
+}
+
+/// the host name, read from env HOSTNAME/COMPUTERNAME or /etc/hostname
+pub fn host_name() -> String {
+    for key in ["HOSTNAME", "COMPUTERNAME"] {
+        if let Ok(v) = std::env::var(key) {
+            if !v.trim().is_empty() {
+                return v.trim().to_string();
+            }
+        }
+    }
+    if let Ok(v) = std::fs::read_to_string("/etc/hostname") {
+        if !v.trim().is_empty() {
+            return v.trim().to_string();
+        }
+    }
+    "localhost".to_string()
+}
+
+/// an archive file of FileSplitAppender
+#[derive(Clone, Debug)]
+pub struct ArchiveFile {
+    pub path: String,
+    pub name: String,
+    /// the time parsed from name
+    pub time: Option<std::time::SystemTime>,
+    /// the index of index naming archive
+    pub index: Option<u64>,
+}
+
+impl ArchiveFile {
+    /// the time parsed from name, or the modified time
+    pub fn time(&self) -> Option<std::time::SystemTime> {
+        self.time
+            .or_else(|| std::fs::metadata(&self.path).and_then(|m| m.modified()).ok())
+    }
+}
+
+/// recognize the archives produced by an FileSplitAppender,
+/// by the name template, the index naming and the packer extension.
+#[derive(Clone, Debug)]
+pub struct ArchiveMatcher {
+    pub temp_name: String,
+    pub template: NameTemplate,
+    /// the packer extension, None accept any extension
+    pub pack_name: Option<String>,
+    /// walk the sub directories, for the date partitioned archives
+    pub recursive: bool,
+    /// the date format of bundle archive, for example temp2026-10-18.zip
+    pub bundle_format: Option<String>,
+    /// the time of the dates in the names, default Local
+    pub time_type: TimeType,
+}
+
+enum Segment {
+    Literal(String),
+    Date,
+    Digits,
+}
+
+impl ArchiveMatcher {
+    pub fn new(temp_name: &str) -> Self {
+        Self {
+            temp_name: temp_name.to_string(),
+            template: NameTemplate::default(),
+            pack_name: None,
+            recursive: false,
+            bundle_format: None,
+            time_type: TimeType::Local,
+        }
+    }
+
+    /// set the name template
+    pub fn template(mut self, template: NameTemplate) -> Self {
+        self.template = template;
+        self
+    }
+
+    /// set the packer extension
+    pub fn pack_name(mut self, pack_name: &str) -> Self {
+        self.pack_name = Some(pack_name.to_string());
+        self
+    }
+
+    /// walk the sub directories
+    pub fn recursive(mut self, recursive: bool) -> Self {
+        self.recursive = recursive;
+        self
+    }
+
+    /// set the date format of bundle archive
+    pub fn bundle_format(mut self, format: &str) -> Self {
+        self.bundle_format = Some(format.to_string());
+        self
+    }
+
+    /// set the time of the dates in the names
+    pub fn time_type(mut self, time_type: TimeType) -> Self {
+        self.time_type = time_type;
+        self
+    }
+
+    /// parse an file name, return None if it is not an archive
+    pub fn parse(&self, file_name: &str) -> Option<ArchiveFile> {
+        if file_name == self.temp_name {
+            return None;
+        }
+        let archive = |time, index| ArchiveFile {
+            path: String::new(),
+            name: file_name.to_string(),
+            time,
+            index,
+        };
+        let (base, suffix) = split_suffix(&self.temp_name);
+        if let (Some(format), Some(pack_name)) = (&self.bundle_format, &self.pack_name) {
+            let date = file_name
+                .strip_prefix(base)
+                .and_then(|x| x.strip_suffix(&format!(".{}", pack_name)));
+            if let Some(date) = date {
+                let len = fastdate::DateTime::from_timestamp(0).format(format).len();
+                if date.len() == len && date.bytes().all(|x| !x.is_ascii_alphabetic()) {
+                    if let Some(time) = parse_date(format, date, &self.time_type) {
+                        return Some(archive(Some(time), None));
+                    }
+                }
+            }
+        }
+        if let Some((index, rest)) =
+            crate::plugin::file_split::archive_index(&self.temp_name, file_name)
+        {
+            let allow = rest.is_empty()
+                || rest == suffix
+                || match &self.pack_name {
+                    Some(pack_name) => rest == format!(".{}", pack_name),
+                    None => rest[1..].find('.').is_none(),
+                };
+            return if allow { Some(archive(None, Some(index))) } else { None };
+        }
+        //the names before packed
+        let mut names = vec![file_name.to_string()];
+        let packed = match &self.pack_name {
+            Some(pack_name) => file_name.strip_suffix(&format!(".{}", pack_name)),
+            None => file_name.rfind('.').map(|idx| &file_name[..idx]),
+        };
+        if let Some(v) = packed {
+            names.push(format!("{}.log", v));
+            names.push(v.to_string());
+        }
+        let segments = self.segments();
+        for name in names {
+            if let Some(time) = match_segments(&segments, &name, &self.template.date_format, &self.time_type) {
+                return Some(archive(time, None));
+            }
+            //the -1,-2... added to avoid collision
+            let (base, suffix) = split_suffix(&name);
+            if let Some(idx) = base.rfind('-') {
+                let seq = &base[idx + 1..];
+                if !seq.is_empty() && seq.bytes().all(|x| x.is_ascii_digit()) {
+                    let name = format!("{}{}", &base[..idx], suffix);
+                    if let Some(time) =
+                        match_segments(&segments, &name, &self.template.date_format, &self.time_type)
+                    {
+                        return Some(archive(time, None));
+                    }
+                }
+            }
+        }
+        None
+    }
+
+    /// the archives of dir, sort by newest first
+    pub fn read_dir(&self, dir: &str) -> Vec<ArchiveFile> {
+        let mut archives = vec![];
+        let mut dirs = vec![std::path::PathBuf::from(dir)];
+        while let Some(dir) = dirs.pop() {
+            if let Ok(paths) = std::fs::read_dir(&dir) {
+                for x in paths.flatten() {
+                    let file_type = match x.file_type() {
+                        Ok(v) => v,
+                        Err(_) => continue,
+                    };
+                    if file_type.is_dir() && self.recursive {
+                        dirs.push(x.path());
+                    }
+                    if !file_type.is_file() {
+                        continue;
+                    }
+                    let name = x.file_name().to_string_lossy().to_string();
+                    if let Some(mut archive) = self.parse(&name) {
+                        archive.path = x.path().to_string_lossy().to_string();
+                        archives.push(archive);
+                    }
+                }
+            }
+        }
+        archives.sort_by(|a, b| match (a.index, b.index) {
+            (Some(a), Some(b)) => a.cmp(&b),
+            _ => match (a.time, b.time) {
+                (Some(a_time), Some(b_time)) if a_time != b_time => b_time.cmp(&a_time),
+                _ => b.name.cmp(&a.name),
+            },
+        });
+        archives
+    }
+
+    /// remove the empty partition directories of dir(not dir self), only when recursive
+    pub fn remove_empty_dirs(&self, dir: &str) {
+        fn remove(dir: &std::path::Path) -> bool {
+            let paths = match std::fs::read_dir(dir) {
+                Ok(v) => v,
+                Err(_) => return false,
+            };
+            let mut empty = true;
+            for x in paths.flatten() {
+                //the partition directory, for example 2026/10/18
+                let is_dir = x.file_type().map(|t| t.is_dir()).unwrap_or(false)
+                    && x.file_name().to_string_lossy().bytes().all(|b| b.is_ascii_digit());
+                if !is_dir || !remove(&x.path()) || std::fs::remove_dir(x.path()).is_err() {
+                    empty = false;
+                }
+            }
+            empty
+        }
+        if self.recursive {
+            remove(std::path::Path::new(dir));
+        }
+    }
+
+    fn segments(&self) -> Vec<Segment> {
+        let (base, suffix) = split_suffix(&self.temp_name);
+        let mut segments = vec![];
+        let mut pattern = self.template.pattern.as_str();
+        while !pattern.is_empty() {
+            let token = ["{base}", "{suffix}", "{date}", "{host}", "{pid}", "{seq}"]
+                .iter()
+                .filter_map(|t| pattern.find(t).map(|idx| (idx, *t)))
+                .min();
+            match token {
+                None => {
+                    segments.push(Segment::Literal(pattern.to_string()));
+                    break;
+                }
+                Some((idx, token)) => {
+                    if idx > 0 {
+                        segments.push(Segment::Literal(pattern[..idx].to_string()));
+                    }
+                    segments.push(match token {
+                        "{base}" => Segment::Literal(base.to_string()),
+                        "{suffix}" => Segment::Literal(suffix.to_string()),
+                        "{host}" => Segment::Literal(host_name()),
+                        "{date}" => Segment::Date,
+                        _ => Segment::Digits,
+                    });
+                    pattern = &pattern[idx + token.len()..];
+                }
+            }
+        }
+        segments
+    }
+}
+
+/// return Some(time of {date}) if matched
+/// parse the date rendered in the time of time_type
+fn parse_date(format: &str, date: &str, time_type: &TimeType) -> Option<std::time::SystemTime> {
+    //`DateTime::parse` reads the date in fastdate::offset_sec(), read it in the offset of that time
+    let time = fastdate::DateTime::parse(format, date).ok()?;
+    let parsed = time.unix_timestamp();
+    let utc = crate::plugin::schedule::to_utc(parsed + fastdate::offset_sec() as i64, time_type);
+    Some(std::time::SystemTime::from(time.add_sub_sec(utc - parsed)))
+}
+
+fn match_segments(
+    segments: &[Segment],
+    name: &str,
+    date_format: &str,
+    time_type: &TimeType,
+) -> Option<Option<std::time::SystemTime>> {
+    let (first, rest) = match segments.split_first() {
+        None => {
+            return if name.is_empty() { Some(None) } else { None };
+        }
+        Some(v) => v,
+    };
+    match first {
+        Segment::Literal(v) => {
+            match_segments(rest, name.strip_prefix(v.as_str())?, date_format, time_type)
+        }
+        Segment::Date => {
+            let len = fastdate::DateTime::from_timestamp(0).format(date_format).len();
+            let date = name.get(..len)?;
+            if date.bytes().any(|x| x.is_ascii_alphabetic() && x != b'T' && x != b'Z') {
+                return None;
+            }
+            let time = parse_date(date_format, date, time_type)?;
+            let matched = match_segments(rest, &name[len..], date_format, time_type)?;
+            Some(matched.or(Some(time)))
+        }
+        Segment::Digits => {
+            let digits = name.bytes().take_while(|x| x.is_ascii_digit()).count();
+            for len in (1..=digits).rev() {
+                if let Some(v) = match_segments(rest, &name[len..], date_format, time_type) {
+                    return Some(v);
+                }
+            }
+            None
+        }
+    }
+}
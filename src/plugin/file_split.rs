@@ -1,9 +1,16 @@
 use crate::appender::{Command, FastLogRecord, LogAppender};
 use crate::consts::LogSize;
-use crate::error::LogError;
-use crate::plugin::file_name::FileName;
-use crate::{chan, Receiver, Sender, WaitGroup};
+use crate::error::{report, LogError, ReportError};
+use crate::plugin::durability::{Durability, SyncState};
+use crate::plugin::file::{file_id, FileId, CHECK_INTERVAL};
+use crate::plugin::file_name::{
+    insert_before_suffix, split_suffix, ArchiveMatcher, FileName, NameTemplate,
+};
+use crate::plugin::pack_pool::{FullPolicy, PackPool};
+use crate::plugin::schedule::{offset_of, Schedule};
+use crate::{chan, Receiver, Sender, TimeType, WaitGroup};
 use fastdate::DateTime;
+use parking_lot::Mutex;
 use std::cell::RefCell;
 use std::fs::{DirEntry, File, OpenOptions};
 use std::io::{Seek, SeekFrom, Write};
@@ -16,7 +23,7 @@ use std::time::{Duration, SystemTime};
 ///
 /// must impl pack_name,is_allow,do_pack method
 pub trait Packer: Send + Sync {
-    fn pack_name(&self) -> &'static str;
+    fn pack_name(&self) -> &str;
 
     ///return bool: remove_log_file
     fn do_pack(&self, log_file: File, log_file_path: &str) -> Result<bool, LogError>;
@@ -25,13 +32,36 @@ pub trait Packer: Send + Sync {
     fn retry(&self) -> i32 {
         return 0;
     }
+
+    /// Some(date format) if the packer append the log files of one date into one archive,
+    /// for example "YYYY-MM-DD" is temp2026-10-18.zip
+    fn bundle_format(&self) -> Option<&str> {
+        None
+    }
+
+    /// the archive path of log file
+    fn archive_path(&self, log_file_path: &str) -> String {
+        pack_path(log_file_path, self.pack_name())
+    }
 }
 
 impl Packer for Box<dyn Packer> {
-    fn pack_name(&self) -> &'static str {
+    fn pack_name(&self) -> &str {
         self.deref().pack_name()
     }
 
+    fn retry(&self) -> i32 {
+        self.deref().retry()
+    }
+
+    fn bundle_format(&self) -> Option<&str> {
+        self.deref().bundle_format()
+    }
+
+    fn archive_path(&self, log_file_path: &str) -> String {
+        self.deref().archive_path(log_file_path)
+    }
+
     fn do_pack(&self, log_file: File, log_file_path: &str) -> Result<bool, LogError> {
         self.deref().do_pack(log_file, log_file_path)
     }
@@ -48,40 +78,88 @@ pub trait CanRollingPack: Send {
         temp_size: usize,
         arg: &FastLogRecord,
     ) -> Option<String>;
+
+    /// called on startup when the active file left by the last process is not empty.
+    /// file_time is the time of its first record(or the modified time), arg is an record of now.
+    /// return Some(log_file_name) to roll it now, default is same as `can`
+    fn can_recover(
+        &mut self,
+        appender: &dyn Packer,
+        temp_name: &str,
+        temp_size: usize,
+        _file_time: SystemTime,
+        arg: &FastLogRecord,
+    ) -> Option<String> {
+        self.can(appender, temp_name, temp_size, arg)
+    }
+
+    /// how the saver names the rolled log file, default NameType::Date(keep the name returned by `can`)
+    fn name_type(&self) -> NameType {
+        NameType::Date
+    }
+
+    /// the name template of the rolled log file, used to recognize the archives
+    fn name_template(&self) -> NameTemplate {
+        NameTemplate::default()
+    }
+
+    /// the time of the dates in the rolled names, used to recognize the archives
+    fn time_type(&self) -> TimeType {
+        TimeType::Local
+    }
+
+    /// the max size of the log file if it rolls by size, the SplitFile may preallocate it
+    fn size_limit(&self) -> Option<usize> {
+        None
+    }
+
+    /// force rolling now(`fast_log::roll()`), return the log_file_name.
+    /// default is rendered by `name_template` with the time of arg
+    fn force(&mut self, temp_name: &str, arg: &FastLogRecord) -> String {
+        let now = DateTime::from_system_time(arg.now, offset_of(arg.now, &self.time_type()));
+        self.name_template().render(temp_name, &now)
+    }
 }
 
+///archive file naming type
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Default)]
+pub enum NameType {
+    /// the name returned by CanRollingPack,for example temp2026-10-18T12-00-00.000000.log
+    #[default]
+    Date,
+    /// temp.log.1, temp.log.2 ... the newest archive is 1,packed to temp.log.1.zip
+    Index,
+    /// temp.1.log, temp.2.log ... the newest archive is 1,packed to temp.1.zip
+    IndexBeforeSuffix,
+}
+
+
 /// keep logs, for example keep by log num or keep by log create time.
 /// that do not meet the retention conditions will be deleted
 /// you can use KeepType or RollingType::All
 pub trait Keep: Send {
     /// return removed nums
     fn do_keep(&self, dir: &str, temp_name: &str) -> i64;
+
+    /// keep the archives recognized by the matcher(the naming of the appender and the packer extension).
+    /// default is `do_keep`
+    fn do_keep_archives(&self, dir: &str, matcher: &ArchiveMatcher) -> i64 {
+        self.do_keep(dir, &matcher.temp_name)
+    }
+
+    /// the archives of temp_name, newest first
     fn read_paths(&self, dir: &str, temp_name: &str) -> Vec<DirEntry> {
-        let base_name = get_base_name(temp_name);
-        let paths = std::fs::read_dir(dir);
-        if let Ok(paths) = paths {
-            //let mut temp_file = None;
-            let mut paths_vec = vec![];
-            for path in paths {
-                match path {
-                    Ok(path) => {
-                        if let Some(v) = path.file_name().to_str() {
-                            if v == temp_name {
-                                continue;
-                            }
-                            if !v.starts_with(&base_name) {
-                                continue;
-                            }
-                        }
-                        paths_vec.push(path);
-                    }
-                    _ => {}
-                }
+        let mut entries = std::collections::HashMap::new();
+        if let Ok(paths) = std::fs::read_dir(dir) {
+            for x in paths.flatten() {
+                entries.insert(x.file_name().to_string_lossy().to_string(), x);
             }
-            paths_vec.sort_by(|a, b| b.file_name().cmp(&a.file_name()));
-            return paths_vec;
         }
-        return vec![];
+        ArchiveMatcher::new(temp_name)
+            .read_dir(dir)
+            .into_iter()
+            .filter_map(|x| entries.remove(&x.name))
+            .collect()
     }
 }
 
@@ -95,6 +173,23 @@ pub trait SplitFile: Send {
     fn flush(&self);
     fn len(&self) -> usize;
     fn offset(&self) -> usize;
+    /// the bytes of records in the file, counted by the size rolling. default `len`
+    fn plain_len(&self) -> usize {
+        self.len()
+    }
+    /// sync the written data to disk(fsync), default do nothing
+    fn sync(&self) -> std::io::Result<()> {
+        Ok(())
+    }
+    /// preallocate the file, called with the size limit of the rolling. default do nothing
+    fn reserve(&self, _len: usize) -> std::io::Result<()> {
+        Ok(())
+    }
+    /// set the file length to the written length before rolling and on exit, for the preallocated file.
+    /// default do nothing
+    fn trim(&self) -> std::io::Result<()> {
+        Ok(())
+    }
 }
 
 ///only use File
@@ -159,6 +254,10 @@ impl SplitFile for RawFile {
         }
         offset
     }
+
+    fn sync(&self) -> std::io::Result<()> {
+        self.inner.borrow().sync_data()
+    }
 }
 
 pub enum DateType {
@@ -197,6 +296,10 @@ impl DurationType {
 pub struct Rolling {
     last: SystemTime,
     pub how: RollingType,
+    pub name_type: NameType,
+    pub name_template: NameTemplate,
+    /// the time of ByDate and the archive name, Local(default) or Utc. BySchedule use the time of Schedule
+    pub time_type: TimeType,
 }
 
 impl Rolling {
@@ -204,8 +307,43 @@ impl Rolling {
         Self {
             last: SystemTime::now(),
             how: how,
+            name_type: NameType::Date,
+            name_template: NameTemplate::default(),
+            time_type: TimeType::Local,
+        }
+    }
+
+    /// use the utc time, for example roll ByDate(DateType::Day) at 00:00 utc
+    pub fn utc(mut self) -> Self {
+        self.time_type = TimeType::Utc;
+        self
+    }
+
+    /// use the local time
+    pub fn local(mut self) -> Self {
+        self.time_type = TimeType::Local;
+        self
+    }
+
+    /// the offset(sec) of the rolling time at `time`, the local offset follows the daylight saving time
+    fn offset_at(&self, time: SystemTime) -> i32 {
+        match &self.how {
+            RollingType::BySchedule(schedule) => schedule.offset_at(time),
+            _ => offset_of(time, &self.time_type),
         }
     }
+
+    /// set the archive name template, for example `NameTemplate::new("{base}-{host}-{date}{suffix}")`
+    pub fn name_template(mut self, template: NameTemplate) -> Self {
+        self.name_template = template;
+        self
+    }
+
+    /// set the archive naming type
+    pub fn name_type(mut self, name_type: NameType) -> Self {
+        self.name_type = name_type;
+        self
+    }
 }
 
 ///log rolling type
@@ -213,9 +351,60 @@ pub enum RollingType {
     ByDate(DateType),
     BySize(LogSize),
     ByDuration((DateTime, Duration)),
+    /// wall clock aligned or cron schedule
+    BySchedule(Schedule),
 }
 
 impl CanRollingPack for Rolling {
+    fn name_type(&self) -> NameType {
+        self.name_type
+    }
+
+    fn name_template(&self) -> NameTemplate {
+        self.name_template.clone()
+    }
+
+    fn time_type(&self) -> TimeType {
+        match &self.how {
+            RollingType::BySchedule(schedule) => schedule.time_type,
+            _ => self.time_type,
+        }
+    }
+
+    fn size_limit(&self) -> Option<usize> {
+        match &self.how {
+            RollingType::BySize(limit) => Some(limit.get_len()),
+            _ => None,
+        }
+    }
+
+    fn force(&mut self, temp_name: &str, arg: &FastLogRecord) -> String {
+        let last = std::mem::replace(&mut self.last, arg.now);
+        let offset = self.offset_at(last);
+        //the schedule keeps its boundaries
+        if let RollingType::ByDuration((start_time, _)) = &mut self.how {
+            *start_time = DateTime::now();
+        }
+        let last_time = DateTime::from_system_time(last, offset);
+        self.name_template.render(temp_name, &last_time)
+    }
+
+    fn can_recover(
+        &mut self,
+        appender: &dyn Packer,
+        temp_name: &str,
+        temp_size: usize,
+        file_time: SystemTime,
+        arg: &FastLogRecord,
+    ) -> Option<String> {
+        //the file is written since file_time
+        self.last = file_time;
+        if let RollingType::BySchedule(schedule) = &mut self.how {
+            schedule.reset();
+        }
+        self.can(appender, temp_name, temp_size, arg)
+    }
+
     fn can(
         &mut self,
         _appender: &dyn Packer,
@@ -223,103 +412,235 @@ impl CanRollingPack for Rolling {
         temp_size: usize,
         arg: &FastLogRecord,
     ) -> Option<String> {
-        let last_time = self.last.clone();
+        let last = self.last.clone();
         self.last = arg.now.clone();
-        return match &mut self.how {
+        let last_time = DateTime::from_system_time(last, self.offset_at(last));
+        let now_offset = self.offset_at(arg.now);
+        let can = match &mut self.how {
             RollingType::ByDate(date_type) => {
-                let last_time = DateTime::from_system_time(last_time, fastdate::offset_sec());
-                let log_time = DateTime::from_system_time(arg.now, fastdate::offset_sec());
-                let diff = match date_type {
+                let log_time = DateTime::from_system_time(arg.now, now_offset);
+                match date_type {
                     DateType::Sec => log_time.sec() != last_time.sec(),
                     DateType::Hour => log_time.hour() != last_time.hour(),
                     DateType::Minute => log_time.minute() != last_time.minute(),
                     DateType::Day => log_time.day() != last_time.day(),
                     DateType::Month => log_time.mon() != last_time.mon(),
                     DateType::Year => log_time.year() != last_time.year(),
-                };
-                if diff {
-                    let log_name = {
-                        if let Some(idx) = temp_name.rfind(".") {
-                            let suffix = &temp_name[idx..];
-                            temp_name.replace(
-                                suffix,
-                                &last_time.format(&format!("YYYY-MM-DDThh-mm-ss.000000{}", suffix)),
-                            )
-                        } else {
-                            let mut temp_name = temp_name.to_string();
-                            temp_name.push_str(&last_time.format("YYYY-MM-DDThh-mm-ss.000000"));
-                            temp_name
-                        }
-                    };
-                    Some(log_name)
-                } else {
-                    None
-                }
-            }
-            RollingType::BySize(limit) => {
-                if temp_size >= limit.get_len() {
-                    let log_name = {
-                        let last_time =
-                            DateTime::from_system_time(last_time, fastdate::offset_sec());
-                        if let Some(idx) = temp_name.rfind(".") {
-                            let suffix = &temp_name[idx..];
-                            temp_name.replace(
-                                suffix,
-                                &last_time.format(&format!("YYYY-MM-DDThh-mm-ss.000000{}", suffix)),
-                            )
-                        } else {
-                            let mut temp_name = temp_name.to_string();
-                            temp_name.push_str(&last_time.format("YYYY-MM-DDThh-mm-ss.000000"));
-                            temp_name
-                        }
-                    };
-                    Some(log_name)
-                } else {
-                    None
                 }
             }
+            RollingType::BySize(limit) => temp_size >= limit.get_len(),
             RollingType::ByDuration((start_time, duration)) => {
                 let log_time = DateTime::from_system_time(arg.now, fastdate::offset_sec());
                 let next = start_time.clone().add(duration.clone());
                 if log_time >= next {
-                    let now = DateTime::now();
-                    let last_time = DateTime::from_system_time(last_time, fastdate::offset_sec());
-                    let log_name = {
-                        if let Some(idx) = temp_name.rfind(".") {
-                            let suffix = &temp_name[idx..];
-                            temp_name.replace(
-                                suffix,
-                                &last_time.format(&format!("YYYY-MM-DDThh-mm-ss.000000{}", suffix)),
-                            )
-                        } else {
-                            let mut temp_name = temp_name.to_string();
-                            temp_name.push_str(&last_time.format("YYYY-MM-DDThh-mm-ss.000000"));
-                            temp_name
-                        }
-                    };
-                    *start_time = now;
-                    Some(log_name)
+                    *start_time = DateTime::now();
+                    true
                 } else {
-                    None
+                    false
                 }
             }
+            RollingType::BySchedule(schedule) => schedule.is_due(last, arg.now),
         };
+        if can {
+            Some(self.name_template.render(temp_name, &last_time))
+        } else {
+            None
+        }
     }
 }
 
+/// combine some CanRollingPack, roll when any of them can roll(or all of them).
+/// a rolling that can roll is remembered until the combined rolling rolls.
+/// for example: roll daily, but also whenever the file exceeds 500 MB
+/// ```rust
+/// use fast_log::consts::LogSize;
+/// use fast_log::plugin::file_split::{CompositeRolling, DateType, Rolling, RollingType};
+/// let rolling = CompositeRolling::any()
+///     .push(Rolling::new(RollingType::ByDate(DateType::Day)))
+///     .push(Rolling::new(RollingType::BySize(LogSize::MB(500))));
+/// ```
+pub struct CompositeRolling {
+    pub rollings: Vec<Box<dyn CanRollingPack>>,
+    /// true: roll when all of rollings can roll, false: roll when any of rollings can roll
+    pub all: bool,
+    pub name_type: NameType,
+    fired: Vec<Option<String>>,
+}
+
+impl CompositeRolling {
+    /// roll when any of rollings can roll
+    pub fn any() -> Self {
+        Self {
+            rollings: vec![],
+            all: false,
+            name_type: NameType::Date,
+            fired: vec![],
+        }
+    }
+
+    /// roll when all of rollings can roll
+    pub fn all() -> Self {
+        let mut s = Self::any();
+        s.all = true;
+        s
+    }
+
+    /// add an rolling
+    pub fn push<R: CanRollingPack + 'static>(mut self, rolling: R) -> Self {
+        self.rollings.push(Box::new(rolling));
+        self.fired.push(None);
+        self
+    }
+
+    /// set the archive naming type
+    pub fn name_type(mut self, name_type: NameType) -> Self {
+        self.name_type = name_type;
+        self
+    }
+}
+
+impl CanRollingPack for CompositeRolling {
+    fn name_type(&self) -> NameType {
+        self.name_type
+    }
+
+    fn name_template(&self) -> NameTemplate {
+        match self.rollings.first() {
+            Some(v) => v.name_template(),
+            None => NameTemplate::default(),
+        }
+    }
+
+    fn time_type(&self) -> TimeType {
+        match self.rollings.first() {
+            Some(v) => v.time_type(),
+            None => TimeType::Local,
+        }
+    }
+
+    fn size_limit(&self) -> Option<usize> {
+        self.rollings.iter().filter_map(|x| x.size_limit()).max()
+    }
+
+    fn can(
+        &mut self,
+        appender: &dyn Packer,
+        temp_name: &str,
+        temp_size: usize,
+        arg: &FastLogRecord,
+    ) -> Option<String> {
+        for (index, rolling) in self.rollings.iter_mut().enumerate() {
+            if let Some(name) = rolling.can(appender, temp_name, temp_size, arg) {
+                if self.fired[index].is_none() {
+                    self.fired[index] = Some(name);
+                }
+            }
+        }
+        self.take_fired()
+    }
+
+    fn force(&mut self, temp_name: &str, arg: &FastLogRecord) -> String {
+        //all of rollings start an new period
+        for (index, rolling) in self.rollings.iter_mut().enumerate() {
+            let name = rolling.force(temp_name, arg);
+            if self.fired[index].is_none() {
+                self.fired[index] = Some(name);
+            }
+        }
+        match self.take_fired() {
+            Some(name) => name,
+            None => {
+                let now = DateTime::from_system_time(arg.now, offset_of(arg.now, &self.time_type()));
+                NameTemplate::default().render(temp_name, &now)
+            }
+        }
+    }
+
+    fn can_recover(
+        &mut self,
+        appender: &dyn Packer,
+        temp_name: &str,
+        temp_size: usize,
+        file_time: SystemTime,
+        arg: &FastLogRecord,
+    ) -> Option<String> {
+        for (index, rolling) in self.rollings.iter_mut().enumerate() {
+            if let Some(name) = rolling.can_recover(appender, temp_name, temp_size, file_time, arg)
+            {
+                if self.fired[index].is_none() {
+                    self.fired[index] = Some(name);
+                }
+            }
+        }
+        self.take_fired()
+    }
+}
+
+impl CompositeRolling {
+    fn take_fired(&mut self) -> Option<String> {
+        let can = if self.all {
+            !self.fired.is_empty() && self.fired.iter().all(|x| x.is_some())
+        } else {
+            self.fired.iter().any(|x| x.is_some())
+        };
+        if !can {
+            return None;
+        }
+        let mut log_name = None;
+        for x in self.fired.iter_mut() {
+            let name = x.take();
+            if log_name.is_none() {
+                log_name = name;
+            }
+        }
+        //the rolls within the same period get the same name, the collision is resolved by the appender(`-N`)
+        log_name
+    }
+}
+
+/// how the active file is rotated when rolling
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Default)]
+pub enum RotateType {
+    /// rename the active file to the archive name and open a new one, constant time.
+    /// fall back to Copy if the rename is impossible(for example an opened file on windows)
+    #[default]
+    Rename,
+    /// copy the active file to the archive name and truncate it, O(file size)
+    Copy,
+}
+
 /// split log file allow pack compress log
 /// Memory space swop running time , reduces the number of repeated queries for IO
 pub struct FileSplitAppender {
     file: Box<dyn SplitFile>,
+    open_file: fn(&str) -> Result<Box<dyn SplitFile>, LogError>,
+    /// the size preallocated by the SplitFile
+    reserve: usize,
     packer: Arc<Box<dyn Packer>>,
     dir_path: String,
     sender: Sender<LogPack>,
     can_pack: Box<dyn CanRollingPack>,
+    rotate_type: RotateType,
+    archive_dir: String,
+    archive_layout: ArchiveLayout,
+    saver: Arc<Mutex<SaverState>>,
+    sync: SyncState,
+    /// the identity of the active file, to detect an external rotation
+    file_id: Option<FileId>,
+    /// the time of last check_file
+    checked: SystemTime,
+    /// the orphans are scanned and the active file left by the last process is rolled
+    /// once the configuration is final(on the first do_logs/do_tick)
+    started: bool,
     //cache data
     temp_bytes: AtomicUsize,
     temp_name: String,
 }
 
+fn open_split_file<F: SplitFile + 'static>(path: &str) -> Result<Box<dyn SplitFile>, LogError> {
+    Ok(Box::new(F::new(path)?))
+}
+
 impl FileSplitAppender {
     pub fn new<F: SplitFile + 'static>(
         file_path: &str,
@@ -348,46 +669,390 @@ impl FileSplitAppender {
         let temp_file = format!("{}{}{}", dir_path, sp, temp_name);
         let temp_bytes = AtomicUsize::new(0);
         let file = F::new(&temp_file)?;
+        let reserve = rolling.size_limit().unwrap_or(0);
+        if let Err(e) = file.reserve(reserve) {
+            report(ReportError::io("FileSplitAppender", "reserve", &temp_file, &e));
+        }
         let mut offset = file.offset();
         if offset != 0 {
             offset += 1;
         }
-        temp_bytes.store(offset, Ordering::Relaxed);
-        let _ = file.seek(SeekFrom::Start(temp_bytes.load(Ordering::Relaxed) as u64));
+        let _ = file.seek(SeekFrom::Start(offset as u64));
+        temp_bytes.store(file.plain_len(), Ordering::Relaxed);
+        let mut matcher = ArchiveMatcher::new(&temp_name)
+            .template(rolling.name_template())
+            .time_type(rolling.time_type())
+            .pack_name(packer.pack_name());
+        if let Some(format) = packer.bundle_format() {
+            matcher = matcher.bundle_format(format);
+        }
+        let saver = Arc::new(Mutex::new(SaverState {
+            matcher,
+            pack_pool: None,
+        }));
         let (sender, receiver) = chan(None);
         let arc_packer = Arc::new(packer);
-        spawn_saver(temp_name.clone(), receiver, keeper, arc_packer.clone());
+        spawn_saver(
+            temp_name.clone(),
+            receiver,
+            keeper,
+            arc_packer.clone(),
+            rolling.name_type(),
+            saver.clone(),
+        );
         Ok(Self {
-            temp_bytes,
             dir_path: dir_path.to_string(),
             file: Box::new(file) as Box<dyn SplitFile>,
+            open_file: open_split_file::<F>,
+            reserve,
             sender,
             can_pack: rolling,
+            rotate_type: RotateType::default(),
+            archive_dir: dir_path.to_string(),
+            archive_layout: ArchiveLayout::default(),
+            saver,
+            sync: SyncState::default(),
+            file_id: file_id(&temp_file),
+            checked: SystemTime::now(),
+            started: false,
+            temp_bytes,
             temp_name,
             packer: arc_packer,
         })
     }
+
+    /// run once the configuration is final, the orphans are scanned before the active file is rolled
+    fn start(&mut self) {
+        if self.started {
+            return;
+        }
+        self.started = true;
+        self.send_orphans();
+        self.recover(&self.temp_path());
+    }
+
+    /// roll the active file left by the last process if the rolling allowed
+    fn recover(&mut self, temp_file: &str) {
+        let temp_size = match self.temp_bytes.load(Ordering::Relaxed) {
+            //the plain bytes of CompressFile are unknown
+            0 => self.file.len(),
+            v => v,
+        };
+        if temp_size == 0 {
+            return;
+        }
+        let now = SystemTime::now();
+        let file_time = first_record_time(temp_file)
+            .or_else(|| std::fs::metadata(temp_file).and_then(|m| m.modified()).ok())
+            .unwrap_or(now);
+        let record = FastLogRecord {
+            command: Command::CommandRecord,
+            level: log::Level::Info,
+            target: String::new(),
+            args: String::new(),
+            module_path: String::new(),
+            file: String::new(),
+            line: None,
+            now,
+            formated: String::new(),
+        };
+        if let Some(new_log_name) = self.can_pack.can_recover(
+            self.packer.deref(),
+            &self.temp_name,
+            temp_size,
+            file_time,
+            &record,
+        ) {
+            self.send_pack(new_log_name, None);
+        }
+    }
+    /// set how the active file is rotated, default RotateType::Rename
+    pub fn rotate_type(mut self, rotate_type: RotateType) -> Self {
+        self.rotate_type = rotate_type;
+        self
+    }
+
+    /// set the directory of archives, default is the directory of the active file.
+    /// the rolled log files are moved there(copy when rename fail) and packed
+    pub fn archive_dir(mut self, dir: &str) -> Self {
+        let _ = std::fs::create_dir_all(dir);
+        self.archive_dir = dir.trim_end_matches('/').to_string();
+        self
+    }
+
+    /// set the layout of archive directory, default ArchiveLayout::Flat
+    pub fn archive_layout(mut self, layout: ArchiveLayout) -> Self {
+        self.archive_layout = layout;
+        self.saver.lock().matcher.recursive = layout != ArchiveLayout::Flat;
+        self
+    }
+
+    /// pack in an shared PackPool instead of the saver thread of this appender,
+    /// the packs of one appender are still done one by one(the workers run the packs of many appenders)
+    pub fn pack_pool(self, pool: PackPool) -> Self {
+        self.saver.lock().pack_pool = Some(pool);
+        self
+    }
+
+    /// set when sync the active file to disk, default Durability::Never.
+    /// the active file is also synced before rolling unless Durability::Never
+    pub fn durability(mut self, durability: Durability) -> Self {
+        self.sync = SyncState::new(durability);
+        self
+    }
+
+    fn sync_data(&mut self) {
+        if let Err(e) = self.file.sync() {
+            report(ReportError::io("FileSplitAppender", "sync", &self.temp_name, &e));
+        }
+        self.sync.synced();
+    }
+
+    /// pack the archives left by an crash before packed
+    fn send_orphans(&mut self) {
+        let matcher = self.saver.lock().matcher.clone();
+        for x in orphan_archives(&self.archive_dir, &matcher) {
+            let _ = self.sender.send(LogPack {
+                dir: self.archive_dir.clone(),
+                new_log_name: x,
+                wg: None,
+            });
+        }
+        if self.archive_dir.trim_end_matches('/') == self.dir_path.trim_end_matches('/') {
+            return;
+        }
+        //the archive_dir may be inside dir_path, only the rolled files of dir_path are moved to archive_dir
+        let matcher = matcher.recursive(false);
+        for x in orphan_archives(&self.dir_path, &matcher) {
+            let new_log_path = self.unique_path(self.archive_path(&x.extract_file_name()));
+            if std::fs::rename(&x, &new_log_path).is_err() {
+                if let Err(e) = std::fs::copy(&x, &new_log_path) {
+                    report(ReportError::io("FileSplitAppender", "copy", &new_log_path, &e));
+                    continue;
+                }
+                let _ = std::fs::remove_file(&x);
+            }
+            let _ = self.sender.send(LogPack {
+                dir: self.archive_dir.clone(),
+                new_log_name: new_log_path,
+                wg: None,
+            });
+        }
+    }
+
+    /// the directory of an new archive
+    fn archive_path(&self, new_log_name: &str) -> String {
+        let mut dir = self.archive_dir.clone();
+        //the index archives are shifted in one directory
+        if self.archive_layout == ArchiveLayout::Date && self.can_pack.name_type() == NameType::Date
+        {
+            let time = self
+                .saver
+                .lock()
+                .matcher
+                .parse(new_log_name)
+                .and_then(|x| x.time)
+                .unwrap_or_else(SystemTime::now);
+            //the date of the name
+            let offset = offset_of(time, &self.saver.lock().matcher.time_type);
+            let partition = DateTime::from_system_time(time, offset).format("YYYY/MM/DD");
+            dir = join_path(&dir, &partition);
+        }
+        if let Err(e) = std::fs::create_dir_all(&dir) {
+            report(ReportError::io("FileSplitAppender", "create_dir", &dir, &e));
+        }
+        join_path(&dir, new_log_name)
+    }
+
+    /// open the active file, and preallocate it
+    fn open_temp(&self, path: &str) -> Result<Box<dyn SplitFile>, LogError> {
+        let file = (self.open_file)(path)?;
+        if let Err(e) = file.reserve(self.reserve) {
+            report(ReportError::io("FileSplitAppender", "reserve", path, &e));
+        }
+        Ok(file)
+    }
+
+    /// set the file length to the written length
+    fn trim(&self) {
+        if let Err(e) = self.file.trim() {
+            report(ReportError::io("FileSplitAppender", "trim", &self.temp_name, &e));
+        }
+    }
+
+    /// the path of the active file
+    fn temp_path(&self) -> String {
+        join_path(&self.dir_path, &self.temp_name)
+    }
+
+    /// reopen the active file if it is moved or removed, or reconcile the size if it is truncated.
+    /// for example by logrotate(create or copytruncate)
+    fn check_file(&mut self, now: SystemTime) {
+        self.checked = now;
+        if file_id(&self.temp_path()) != self.file_id {
+            self.reopen_file();
+            return;
+        }
+        //the write position is beyond the end
+        let len = self.file.len();
+        if matches!(self.file.seek(SeekFrom::Current(0)), Ok(pos) if pos as usize > len) {
+            if let Err(e) = self.file.seek(SeekFrom::End(0)) {
+                report(ReportError::io("FileSplitAppender", "seek", &self.temp_name, &e));
+            }
+            self.temp_bytes.store(self.file.plain_len(), Ordering::SeqCst);
+        }
+    }
+
+    /// open the active file by path, the records are appended to it
+    fn reopen_file(&mut self) {
+        self.file.flush();
+        let temp_path = self.temp_path();
+        match self.open_temp(&temp_path) {
+            Ok(file) => {
+                let len = file.len();
+                if let Err(e) = file.seek(SeekFrom::Start(len as u64)) {
+                    report(ReportError::io("FileSplitAppender", "seek", &temp_path, &e));
+                }
+                self.temp_bytes.store(file.plain_len(), Ordering::SeqCst);
+                self.file = file;
+                self.file_id = file_id(&temp_path);
+            }
+            Err(e) => report(ReportError::new(
+                "FileSplitAppender",
+                "reopen",
+                &temp_path,
+                e.to_string(),
+            )),
+        }
+    }
+
     /// send data make an pack,and truncate data when finish.
-    pub fn send_pack(&self, new_log_name: String, wg: Option<WaitGroup>) {
-        let mut sp = "";
-        if !self.dir_path.is_empty() && !self.dir_path.ends_with("/") {
-            sp = "/";
+    /// takes `&mut self`(breaking, it was `&self`), the active file is renamed and reopened by `RotateType::Rename`
+    pub fn send_pack(&mut self, new_log_name: String, wg: Option<WaitGroup>) {
+        let first_file_path = self.temp_path();
+        let new_log_path = self.unique_path(self.archive_path(&new_log_name));
+        let mut full_policy = None;
+        let pack_pool = self.saver.lock().pack_pool.clone();
+        if let Some(pool) = pack_pool {
+            if pool.is_full(self.sender.len()) {
+                match pool.full_policy {
+                    FullPolicy::Block => pool.wait(|| self.sender.len()),
+                    policy => full_policy = Some(policy),
+                }
+            }
         }
-        let first_file_path = format!("{}{}{}", self.dir_path, sp, &self.temp_name);
-        let new_log_path = first_file_path.replace(&self.temp_name, &new_log_name);
         self.file.flush();
-        let _ = std::fs::copy(&first_file_path, &new_log_path);
-        let _ = self.sender.send(LogPack {
-            dir: self.dir_path.clone(),
-            new_log_name: new_log_path,
-            wg,
-        });
-        self.truncate();
+        if self.sync.durability != Durability::Never {
+            self.sync_data();
+        }
+        self.trim();
+        let renamed = match self.rotate_type {
+            RotateType::Rename => match self.rename_file(&first_file_path, &new_log_path) {
+                Ok(v) => v,
+                Err(e) => {
+                    //the active file is at new_log_path and still written, it is reopened by the next check
+                    report(ReportError::new(
+                        "FileSplitAppender",
+                        "rename",
+                        &new_log_path,
+                        e.to_string(),
+                    ));
+                    return;
+                }
+            },
+            RotateType::Copy => false,
+        };
+        if !renamed {
+            if let Err(e) = std::fs::copy(&first_file_path, &new_log_path) {
+                report(ReportError::io("FileSplitAppender", "copy", &new_log_path, &e));
+            }
+        }
+        match full_policy {
+            //the log file is kept as an archive, and packed by the next startup
+            Some(FullPolicy::KeepUncompressed) => {}
+            Some(_) => {
+                if let Err(e) = std::fs::remove_file(&new_log_path) {
+                    report(ReportError::io("FileSplitAppender", "remove", &new_log_path, &e));
+                }
+            }
+            None => {
+                let _ = self.sender.send(LogPack {
+                    dir: self.archive_dir.clone(),
+                    new_log_name: new_log_path,
+                    wg,
+                });
+            }
+        }
+        if !renamed {
+            self.truncate();
+        }
+    }
+
+    /// rename the active file and open a new one.
+    /// return false if not renamed(the active file is not changed), Err if the active file can not be restored
+    fn rename_file(&mut self, file_path: &str, new_log_path: &str) -> Result<bool, LogError> {
+        if std::fs::rename(file_path, new_log_path).is_err() {
+            return Ok(false);
+        }
+        match self.open_temp(file_path) {
+            Ok(file) => {
+                self.file = file;
+                self.temp_bytes.store(0, Ordering::SeqCst);
+                self.file_id = file_id(file_path);
+                Ok(true)
+            }
+            Err(e) => {
+                //keep writing the old file
+                std::fs::rename(new_log_path, file_path).map_err(|restore| {
+                    LogError::from(format!("open fail: {}, restore fail: {}", e, restore))
+                })?;
+                Ok(false)
+            }
+        }
+    }
+
+    /// avoid overwriting an exist archive(or its pack), for example temp.log => temp-1.log.
+    /// the `-N` continues from the highest one on disk(left by the last process too)
+    fn unique_path(&self, path: String) -> String {
+        let exists = |p: &str| {
+            std::path::Path::new(p).exists()
+                || std::path::Path::new(&pack_path(p, self.packer.pack_name())).exists()
+        };
+        if !exists(&path) {
+            return path;
+        }
+        let mut seq = max_seq(&path) + 1;
+        loop {
+            let new_path = insert_before_suffix(&path, &format!("-{}", seq));
+            if !exists(&new_path) {
+                return new_path;
+            }
+            seq += 1;
+        }
+    }
+
+    /// write the active file, return the written bytes
+    fn write_temp(&self, buf: &[u8]) -> usize {
+        if buf.is_empty() {
+            return 0;
+        }
+        match self.file.write(buf) {
+            Ok(w) => {
+                self.temp_bytes.fetch_add(w, Ordering::SeqCst);
+                w
+            }
+            Err(e) => {
+                report(ReportError::io("FileSplitAppender", "write", &self.temp_name, &e));
+                0
+            }
+        }
     }
 
     pub fn truncate(&self) {
         //reset data
-        let _ = self.file.truncate();
+        if let Err(e) = self.file.truncate() {
+            report(ReportError::io("FileSplitAppender", "truncate", &self.temp_name, &e));
+        }
         self.temp_bytes.store(0, Ordering::SeqCst);
     }
 
@@ -410,31 +1075,56 @@ impl LogPack {
         if log_file_path.is_empty() {
             return Err(LogError::from("log_file_path.is_empty"));
         }
-        let log_file = OpenOptions::new()
-            .write(true)
-            .read(true)
-            .open(log_file_path)
-            .map_err(|e| {
-                LogError::from(format!("open(log_file_path={}) fail={}", log_file_path, e))
-            })?;
+        let pack = || {
+            let log_file = OpenOptions::new()
+                .write(true)
+                .read(true)
+                .open(log_file_path)
+                .map_err(|e| {
+                    LogError::from(format!("open(log_file_path={}) fail={}", log_file_path, e))
+                })?;
+            packer.do_pack(log_file, log_file_path)
+        };
+        #[cfg(feature = "manifest")]
+        let entry = crate::plugin::manifest::ManifestEntry::scan_log(log_file_path);
         //make
-        let r = packer.do_pack(log_file, log_file_path);
-        if r.is_err() && packer.retry() > 0 {
-            let mut retry = 1;
-            while let Err(_packs) = self.do_pack(packer) {
-                retry += 1;
-                if retry > packer.retry() {
-                    break;
-                }
-            }
+        let mut r = pack();
+        let mut retry = 0;
+        while r.is_err() && retry < packer.retry() {
+            retry += 1;
+            r = pack();
         }
-        if let Ok(b) = r {
-            return Ok(b);
+        #[cfg(feature = "manifest")]
+        if let (Ok(_), Ok(mut entry)) = (&r, entry) {
+            let mut archive_path = packer.archive_path(log_file_path);
+            if !std::path::Path::new(&archive_path).exists() {
+                //the packer keep log file
+                archive_path = log_file_path.to_string();
+            }
+            if packer.bundle_format().is_some() {
+                entry.member = log_file_path.extract_file_name();
+            }
+            let recorded = crate::plugin::manifest::fill_archive(&mut entry, &self.dir, &archive_path)
+                .and_then(|_| crate::plugin::manifest::append(&self.dir, &entry.to_json()));
+            if let Err(e) = recorded {
+                report(ReportError::new("Manifest", "record", &archive_path, e.to_string()));
+            }
         }
-        return Ok(false);
+        r
     }
 }
 
+/// the layout of archive directory
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Default)]
+pub enum ArchiveLayout {
+    /// all archives in the archive directory
+    #[default]
+    Flat,
+    /// partitioned by the date of archive,for example archive/2026/10/18/temp2026-10-18T12-00-00.000000.zip.
+    /// the index archives(NameType::Index) are not partitioned
+    Date,
+}
+
 ///rolling keep type
 #[derive(Copy, Clone, Debug)]
 pub enum KeepType {
@@ -447,41 +1137,126 @@ pub enum KeepType {
     KeepTime(Duration),
     /// keep log pack num(.log,.zip.lz4...more)
     KeepNum(i64),
+    /// keep the newest log packs of total size, delete the oldest ones when exceeded
+    KeepSize(LogSize),
 }
 
 impl Keep for KeepType {
     fn do_keep(&self, dir: &str, temp_name: &str) -> i64 {
+        self.do_keep_archives(dir, &ArchiveMatcher::new(temp_name))
+    }
+
+    fn do_keep_archives(&self, dir: &str, matcher: &ArchiveMatcher) -> i64 {
         let mut removed = 0;
         match self {
             KeepType::All => {
                 //do nothing
             }
             KeepType::KeepNum(n) => {
-                let paths_vec = self.read_paths(dir, temp_name);
-                for index in 0..paths_vec.len() {
+                let archives = matcher.read_dir(dir);
+                for (index, item) in archives.iter().enumerate() {
                     if index >= (*n) as usize {
-                        let item = &paths_vec[index];
-                        let _ = std::fs::remove_file(item.path());
-                        removed += 1;
+                        remove_archive(dir, &item.path, &mut removed);
                     }
                 }
             }
             KeepType::KeepTime(duration) => {
-                let paths_vec = self.read_paths(dir, temp_name);
-                let now = DateTime::now();
-                for index in 0..paths_vec.len() {
-                    let item = &paths_vec[index];
-                    if let Ok(m) = item.metadata() {
-                        if let Ok(c) = m.created() {
-                            let time = DateTime::from(c);
-                            if now.clone().sub(duration.clone()) > time {
-                                let _ = std::fs::remove_file(item.path());
-                                removed += 1;
-                            }
+                let archives = matcher.read_dir(dir);
+                let now = SystemTime::now();
+                for item in archives {
+                    //the time of name, or the modified time
+                    if let Some(time) = item.time() {
+                        if now.duration_since(time).unwrap_or_default() > *duration {
+                            remove_archive(dir, &item.path, &mut removed);
                         }
                     }
                 }
             }
+            KeepType::KeepSize(limit) => {
+                let archives = matcher.read_dir(dir);
+                let mut total = 0;
+                for item in archives {
+                    total += std::fs::metadata(&item.path)
+                        .map(|m| m.len() as usize)
+                        .unwrap_or_default();
+                    if total > limit.get_len() {
+                        remove_archive(dir, &item.path, &mut removed);
+                    }
+                }
+            }
+        }
+        if removed > 0 {
+            matcher.remove_empty_dirs(dir);
+        }
+        removed
+    }
+}
+
+fn remove_archive(_dir: &str, path: &str, removed: &mut i64) {
+    match std::fs::remove_file(path) {
+        Ok(_) => {
+            *removed += 1;
+            #[cfg(feature = "manifest")]
+            if let Err(e) = crate::plugin::manifest::record_removed(_dir, path) {
+                report(ReportError::new("Manifest", "record", path, e.to_string()));
+            }
+        }
+        Err(e) => report(ReportError::io("Keep", "remove", path, &e)),
+    }
+}
+
+/// combine KeepTypes into one policy, an log pack is removed if any of them not keep it.
+/// for example: keep 30 packs, at most 7 days and 10GB
+/// ```rust
+/// use std::time::Duration;
+/// use fast_log::consts::LogSize;
+/// use fast_log::plugin::file_split::KeepPolicy;
+/// let keep = KeepPolicy::new()
+///     .keep_num(30)
+///     .keep_time(Duration::from_secs(7 * 24 * 3600))
+///     .keep_size(LogSize::GB(10));
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct KeepPolicy {
+    pub keeps: Vec<KeepType>,
+}
+
+impl KeepPolicy {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// add an KeepType
+    pub fn keep(mut self, keep: KeepType) -> Self {
+        self.keeps.push(keep);
+        self
+    }
+
+    /// limit the log pack num
+    pub fn keep_num(self, num: i64) -> Self {
+        self.keep(KeepType::KeepNum(num))
+    }
+
+    /// limit the log pack age
+    pub fn keep_time(self, duration: Duration) -> Self {
+        self.keep(KeepType::KeepTime(duration))
+    }
+
+    /// limit the total size of log packs
+    pub fn keep_size(self, size: LogSize) -> Self {
+        self.keep(KeepType::KeepSize(size))
+    }
+}
+
+impl Keep for KeepPolicy {
+    fn do_keep(&self, dir: &str, temp_name: &str) -> i64 {
+        self.do_keep_archives(dir, &ArchiveMatcher::new(temp_name))
+    }
+
+    fn do_keep_archives(&self, dir: &str, matcher: &ArchiveMatcher) -> i64 {
+        let mut removed = 0;
+        for x in &self.keeps {
+            removed += x.do_keep_archives(dir, matcher);
         }
         removed
     }
@@ -489,6 +1264,13 @@ impl Keep for KeepType {
 
 impl LogAppender for FileSplitAppender {
     fn do_logs(&mut self, records: &[FastLogRecord]) {
+        self.start();
+        //the tick is not called on an busy logger
+        if let Some(x) = records.last() {
+            if x.now.duration_since(self.checked).unwrap_or_default() >= CHECK_INTERVAL {
+                self.check_file(x.now);
+            }
+        }
         //if temp_bytes is full,must send pack
         let cap = records.iter().map(|record| record.formated.len()).sum();
         let mut temp = String::with_capacity(cap);
@@ -504,24 +1286,22 @@ impl LogAppender for FileSplitAppender {
                         current_temp_size,
                         x,
                     ) {
-                        self.temp_bytes.fetch_add(
-                            {
-                                let w = self.file.write(temp.as_bytes());
-                                if let Ok(w) = w {
-                                    w
-                                } else {
-                                    0
-                                }
-                            },
-                            Ordering::SeqCst,
-                        );
+                        self.write_temp(temp.as_bytes());
                         temp.clear();
                         self.send_pack(new_log_name, None);
                     }
                     temp.push_str(x.formated.as_str());
                 }
-                Command::CommandExit => {}
+                Command::CommandExit => {
+                    self.write_temp(temp.as_bytes());
+                    temp.clear();
+                    self.file.flush();
+                    self.trim();
+                }
                 Command::CommandFlush(ref w) => {
+                    self.write_temp(temp.as_bytes());
+                    temp.clear();
+                    self.file.flush();
                     let current_temp_size = self.temp_bytes.load(Ordering::Relaxed);
                     if let Some(new_log_name) = self.can_pack.can(
                         self.packer.deref(),
@@ -529,37 +1309,70 @@ impl LogAppender for FileSplitAppender {
                         current_temp_size,
                         x,
                     ) {
-                        self.temp_bytes.fetch_add(
-                            {
-                                let w = self.file.write(temp.as_bytes());
-                                if let Ok(w) = w {
-                                    w
-                                } else {
-                                    0
-                                }
-                            },
-                            Ordering::SeqCst,
-                        );
-                        temp.clear();
                         self.send_pack(new_log_name, Some(w.clone()));
                     }
                 }
+                Command::CommandRoll(ref w) => {
+                    self.write_temp(temp.as_bytes());
+                    temp.clear();
+                    //nothing to roll
+                    if self.temp_bytes.load(Ordering::Relaxed) != 0 {
+                        let new_log_name = self.can_pack.force(&self.temp_name, x);
+                        self.send_pack(new_log_name, Some(w.clone()));
+                    }
+                }
+                //synced after written
+                Command::CommandSync(_) => {}
             }
         }
         if !temp.is_empty() {
-            let _ = self.temp_bytes.fetch_add(
-                {
-                    let w = self.file.write(temp.as_bytes());
-                    if let Ok(w) = w {
-                        w
-                    } else {
-                        0
-                    }
-                },
-                Ordering::SeqCst,
-            );
+            self.write_temp(temp.as_bytes());
+        }
+        if self.sync.need_sync(records) {
+            self.sync_data();
         }
     }
+
+    fn do_tick(&mut self, now: SystemTime) {
+        self.start();
+        let record = FastLogRecord {
+            command: Command::CommandRecord,
+            level: log::Level::Info,
+            target: String::new(),
+            args: String::new(),
+            module_path: String::new(),
+            file: String::new(),
+            line: None,
+            now,
+            formated: String::new(),
+        };
+        let temp_size = self.temp_bytes.load(Ordering::Relaxed);
+        if let Some(new_log_name) =
+            self.can_pack
+                .can(self.packer.deref(), &self.temp_name, temp_size, &record)
+        {
+            //nothing to roll
+            if temp_size != 0 {
+                self.send_pack(new_log_name, None);
+            }
+        }
+        //write the buffered data of SplitFile, for example the frame of CompressFile
+        self.file.flush();
+        if self.sync.need_sync_tick() {
+            self.sync_data();
+        }
+        self.check_file(now);
+    }
+
+    fn reopen(&mut self) {
+        self.reopen_file();
+    }
+}
+
+/// the state shared by FileSplitAppender and its saver thread
+struct SaverState {
+    matcher: ArchiveMatcher,
+    pack_pool: Option<PackPool>,
 }
 
 ///spawn an saver thread to save log file or zip file
@@ -568,23 +1381,63 @@ fn spawn_saver(
     r: Receiver<LogPack>,
     rolling_type: Box<dyn Keep>,
     packer: Arc<Box<dyn Packer>>,
+    name_type: NameType,
+    state: Arc<Mutex<SaverState>>,
 ) {
+    let rolling_type = Arc::new(Mutex::new(rolling_type));
     std::thread::spawn(move || {
         loop {
-            if let Ok(pack) = r.recv() {
-                if pack.wg.is_some() {
-                    return;
+            if let Ok(mut pack) = r.recv() {
+                //the wg of flush or roll is done when the pack is dropped(after packed and kept)
+                if name_type != NameType::Date
+                    && archive_index(&temp_name, &pack.new_log_name.extract_file_name()).is_none()
+                {
+                    match rename_to_index(&pack.dir, &temp_name, &pack.new_log_name, name_type)
+                    {
+                        Ok(v) => pack.new_log_name = v,
+                        Err(e) => report(ReportError::io(
+                            "FileSplitAppender",
+                            "rename",
+                            &pack.new_log_name,
+                            &e,
+                        )),
+                    }
                 }
-                let log_file_path = pack.new_log_name.clone();
-                //do save pack
-                let remove = pack.do_pack(packer.as_ref());
-                if let Ok(remove) = remove {
-                    if remove {
-                        let _ = std::fs::remove_file(log_file_path);
+                let (matcher, pack_pool) = {
+                    let state = state.lock();
+                    (state.matcher.clone(), state.pack_pool.clone())
+                };
+                let packer = packer.clone();
+                let rolling_type = rolling_type.clone();
+                let job = move || {
+                    //the jobs of one appender are serialized(on the shared PackPool too),
+                    //the keep must not remove or renumber the archives that another job is packing
+                    let keep = rolling_type.lock();
+                    let log_file_path = pack.new_log_name.clone();
+                    //do save pack
+                    match pack.do_pack(packer.as_ref()) {
+                        Ok(true) => {
+                            if let Err(e) = std::fs::remove_file(&log_file_path) {
+                                report(ReportError::io("Packer", "remove", &log_file_path, &e));
+                            }
+                        }
+                        Ok(false) => {}
+                        Err(e) => report(ReportError::new(
+                            "Packer",
+                            "pack",
+                            &log_file_path,
+                            e.to_string(),
+                        )),
                     }
+                    //do rolling
+                    keep.do_keep_archives(&pack.dir, &matcher);
+                };
+                match pack_pool {
+                    None => job(),
+                    //the index archives must be renamed after packed
+                    Some(pool) if name_type != NameType::Date => pool.submit_wait(job),
+                    Some(pool) => pool.submit(job),
                 }
-                //do rolling
-                rolling_type.do_keep(&pack.dir, &temp_name);
             } else {
                 break;
             }
@@ -592,6 +1445,34 @@ fn spawn_saver(
     });
 }
 
+/// the highest `-N` of the archives of path, for example temp.log => 2 of temp-2.log(or temp-2.zip)
+fn max_seq(path: &str) -> u64 {
+    let name = path.extract_file_name();
+    let dir = &path[..path.len() - name.len()];
+    let dir = if dir.is_empty() { "." } else { dir };
+    let (base, _) = split_suffix(&name);
+    let prefix = format!("{}-", base);
+    let mut max = 0;
+    if let Ok(entries) = std::fs::read_dir(dir) {
+        for x in entries.flatten() {
+            let file_name = x.file_name().to_string_lossy().to_string();
+            let rest = match file_name.strip_prefix(&prefix) {
+                Some(v) => v,
+                None => continue,
+            };
+            let digits = rest.bytes().take_while(|x| x.is_ascii_digit()).count();
+            //the suffix or the pack extension follows
+            if digits == 0 || !(digits == rest.len() || rest[digits..].starts_with('.')) {
+                continue;
+            }
+            if let Ok(seq) = rest[..digits].parse::<u64>() {
+                max = max.max(seq);
+            }
+        }
+    }
+    max
+}
+
 fn get_base_name(path: &str) -> String {
     let file_name = path.extract_file_name();
     let p = file_name.rfind(".");
@@ -600,3 +1481,120 @@ fn get_base_name(path: &str) -> String {
         Some(i) => file_name[0..i].to_string(),
     }
 }
+
+fn join_path(dir: &str, name: &str) -> String {
+    if dir.is_empty() || dir.ends_with("/") {
+        format!("{}{}", dir, name)
+    } else {
+        format!("{}/{}", dir, name)
+    }
+}
+
+/// the pack file path of log_file_path,for example temp.log => temp.zip, temp.log.1 => temp.log.1.zip
+pub fn pack_path(log_file_path: &str, pack_name: &str) -> String {
+    match log_file_path.strip_suffix(".log") {
+        Some(v) => format!("{}.{}", v, pack_name),
+        None => format!("{}.{}", log_file_path, pack_name),
+    }
+}
+
+/// parse the index of an index archive name,
+/// for example (temp.log, temp.log.2.zip) => (2, ".zip"), (temp.log, temp.2.log) => (2, ".log")
+pub fn archive_index(temp_name: &str, file_name: &str) -> Option<(u64, String)> {
+    let base_name = get_base_name(temp_name);
+    for prefix in [format!("{}.", temp_name), format!("{}.", base_name)] {
+        if let Some(rest) = file_name.strip_prefix(&prefix) {
+            let end = rest.find(".").unwrap_or(rest.len());
+            let index = &rest[..end];
+            if index.is_empty() || !index.bytes().all(|x| x.is_ascii_digit()) {
+                continue;
+            }
+            if prefix.len() == base_name.len() + 1 && end == rest.len() && base_name != temp_name {
+                //temp.1 is not an archive of temp.log
+                continue;
+            }
+            if let Ok(index) = index.parse() {
+                return Some((index, rest[end..].to_string()));
+            }
+        }
+    }
+    None
+}
+
+/// shift the index archives(1=>2,2=>3...) and rename the rolled log file to index 1
+fn rename_to_index(
+    dir: &str,
+    temp_name: &str,
+    log_file_path: &str,
+    name_type: NameType,
+) -> std::io::Result<String> {
+    let mut archives = vec![];
+    for x in std::fs::read_dir(dir)? {
+        let name = x?.file_name().to_string_lossy().to_string();
+        if let Some((index, rest)) = archive_index(temp_name, &name) {
+            archives.push((index, rest, name));
+        }
+    }
+    archives.sort_by_key(|x| std::cmp::Reverse(x.0));
+    let mut renames = vec![];
+    for (index, rest, name) in archives {
+        let new_name = index_name(temp_name, index + 1, &rest, name_type);
+        std::fs::rename(join_path(dir, &name), join_path(dir, &new_name))?;
+        renames.push((name, new_name));
+    }
+    #[cfg(feature = "manifest")]
+    if !renames.is_empty() {
+        if let Err(e) = crate::plugin::manifest::record_renamed(dir, &renames) {
+            report(ReportError::new("Manifest", "record", dir, e.to_string()));
+        }
+    }
+    let suffix = match temp_name.rfind(".") {
+        Some(idx) if name_type == NameType::IndexBeforeSuffix => &temp_name[idx..],
+        _ => "",
+    };
+    let new_path = join_path(dir, &index_name(temp_name, 1, suffix, name_type));
+    std::fs::rename(log_file_path, &new_path)?;
+    Ok(new_path)
+}
+
+fn index_name(temp_name: &str, index: u64, rest: &str, name_type: NameType) -> String {
+    match name_type {
+        NameType::IndexBeforeSuffix if temp_name.contains(".") => {
+            format!("{}.{}{}", get_base_name(temp_name), index, rest)
+        }
+        _ => format!("{}.{}{}", temp_name, index, rest),
+    }
+}
+
+/// the time of the first record of log file, support FastLogFormat and FastLogFormatJson
+fn first_record_time(path: &str) -> Option<SystemTime> {
+    use std::io::Read;
+    let mut buf = [0u8; 256];
+    let len = File::open(path).ok()?.read(&mut buf).ok()?;
+    let head = String::from_utf8_lossy(&buf[..len]);
+    let date = match head.find("\"date\":\"") {
+        Some(idx) => head.get(idx + 8..idx + 34)?,
+        None => head.get(0..26)?,
+    };
+    //the date is rendered by FastLogFormat in the local time, `DateTime::parse` applies fastdate::offset_sec()
+    let time = DateTime::parse("YYYY-MM-DD hh:mm:ss.000000", date).ok()?;
+    Some(SystemTime::from(time))
+}
+
+/// the rolled log files that are not packed, for example temp2026-10-18T12-00-00.000000.log
+fn orphan_archives(dir: &str, matcher: &ArchiveMatcher) -> Vec<String> {
+    let mut orphans = vec![];
+    let pack_name = matcher.pack_name.clone().unwrap_or_default();
+    if pack_path(&matcher.temp_name, &pack_name) == matcher.temp_name {
+        //the packer keep log file
+        return orphans;
+    }
+    let pack_suffix = format!(".{}", pack_name);
+    for x in matcher.read_dir(dir) {
+        if !x.name.ends_with(&pack_suffix) {
+            orphans.push(x.path);
+        }
+    }
+    orphans.sort();
+    orphans
+}
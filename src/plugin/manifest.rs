@@ -0,0 +1,359 @@
+use crate::error::LogError;
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, Read, Write};
+
+/// the manifest file name in the archive directory, one json per line
+pub const MANIFEST_NAME: &str = "manifest.jsonl";
+
+/// the manifest is appended and rewritten by many pack jobs
+static LOCK: parking_lot::Mutex<()> = parking_lot::const_mutex(());
+
+/// you need enable fast_log = { ... ,features=["manifest"]}
+/// an manifest entry of archive, written by `LogPack::do_pack`
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ManifestEntry {
+    /// the archive path relative to the archive directory
+    pub archive: String,
+    /// the log file name in an bundle archive(for example the daily zip), empty if the archive is one log file
+    pub member: String,
+    /// the size of log file before packed
+    pub original_size: u64,
+    /// the size of archive
+    pub size: u64,
+    /// the sha256 hex of archive
+    pub sha256: String,
+    /// the time of first record, for example 2026-10-18 12:00:00.000000
+    pub first: String,
+    /// the time of last record
+    pub last: String,
+    /// record num of levels, for example INFO => 10
+    pub levels: BTreeMap<String, u64>,
+}
+
+impl ManifestEntry {
+    /// scan the records of an log file(FastLogFormat or FastLogFormatJson)
+    pub fn scan_log(path: &str) -> std::io::Result<Self> {
+        let file = File::open(path)?;
+        let mut entry = ManifestEntry {
+            original_size: file.metadata()?.len(),
+            ..Default::default()
+        };
+        let mut reader = BufReader::new(file);
+        let mut line = vec![];
+        loop {
+            line.clear();
+            if reader.read_until(b'\n', &mut line)? == 0 {
+                break;
+            }
+            let line = String::from_utf8_lossy(&line);
+            if let Some((date, level)) = parse_record(&line) {
+                if entry.first.is_empty() {
+                    entry.first = date.to_string();
+                }
+                entry.last = date.to_string();
+                *entry.levels.entry(level.to_string()).or_default() += 1;
+            }
+        }
+        Ok(entry)
+    }
+
+    /// the key of entry, the archive(and the member of bundle)
+    pub fn key(&self) -> String {
+        if self.member.is_empty() {
+            self.archive.clone()
+        } else {
+            format!("{}#{}", self.archive, self.member)
+        }
+    }
+
+    pub fn to_json(&self) -> String {
+        let levels: Vec<String> = self
+            .levels
+            .iter()
+            .map(|(k, v)| format!("\"{}\":{}", escape(k), v))
+            .collect();
+        let member = if self.member.is_empty() {
+            String::new()
+        } else {
+            format!("\"member\":\"{}\",", escape(&self.member))
+        };
+        format!(
+            "{{\"archive\":\"{}\",{}\"original_size\":{},\"size\":{},\"sha256\":\"{}\",\"first\":\"{}\",\"last\":\"{}\",\"levels\":{{{}}}}}",
+            escape(&self.archive),
+            member,
+            self.original_size,
+            self.size,
+            self.sha256,
+            escape(&self.first),
+            escape(&self.last),
+            levels.join(",")
+        )
+    }
+
+    /// parse an line of `to_json`
+    pub fn from_json(line: &str) -> Option<Self> {
+        let mut entry = ManifestEntry {
+            archive: json_str(line, "archive")?,
+            member: json_str(line, "member").unwrap_or_default(),
+            original_size: json_num(line, "original_size")?,
+            size: json_num(line, "size")?,
+            sha256: json_str(line, "sha256")?,
+            first: json_str(line, "first").unwrap_or_default(),
+            last: json_str(line, "last").unwrap_or_default(),
+            levels: BTreeMap::new(),
+        };
+        let levels = line.find("\"levels\":{").map(|idx| &line[idx + 10..])?;
+        let levels = &levels[..levels.find('}')?];
+        for x in levels.split(',').filter(|x| !x.is_empty()) {
+            let (k, v) = x.split_once(':')?;
+            entry
+                .levels
+                .insert(k.trim_matches('"').to_string(), v.parse().ok()?);
+        }
+        Some(entry)
+    }
+}
+
+/// the result of `verify`
+#[derive(Clone, Debug, Default)]
+pub struct VerifyReport {
+    /// the archives matched the manifest
+    pub ok: Vec<String>,
+    /// the archives not exist and not removed by Keep
+    pub missing: Vec<String>,
+    /// the archives size or sha256 changed
+    pub corrupted: Vec<String>,
+}
+
+impl VerifyReport {
+    pub fn is_ok(&self) -> bool {
+        self.missing.is_empty() && self.corrupted.is_empty()
+    }
+}
+
+/// record the archive(relative to dir) into the manifest of dir
+pub fn record(dir: &str, log_file_path: &str, archive_path: &str) -> Result<(), LogError> {
+    let mut entry = ManifestEntry::scan_log(log_file_path)?;
+    fill_archive(&mut entry, dir, archive_path)?;
+    append(dir, &entry.to_json())
+}
+
+/// record the archive is packed, the log file is scanned before packed
+pub fn fill_archive(
+    entry: &mut ManifestEntry,
+    dir: &str,
+    archive_path: &str,
+) -> Result<(), LogError> {
+    entry.archive = relative(dir, archive_path);
+    entry.size = std::fs::metadata(archive_path)?.len();
+    entry.sha256 = sha256_file(archive_path)?;
+    Ok(())
+}
+
+/// record the archive is removed by Keep, the manifest is compacted
+pub fn record_removed(dir: &str, archive_path: &str) -> Result<(), LogError> {
+    let name = relative(dir, archive_path);
+    rewrite(dir, |entries| entries.retain(|_, x| x.archive != name))
+}
+
+/// record the archives are renamed(the index archives are shifted), the manifest is compacted
+pub fn record_renamed(dir: &str, renames: &[(String, String)]) -> Result<(), LogError> {
+    rewrite(dir, |entries| {
+        for (from, to) in renames {
+            rename(entries, &relative(dir, from), &relative(dir, to));
+        }
+    })
+}
+
+/// append an line to the manifest of dir
+pub fn append(dir: &str, line: &str) -> Result<(), LogError> {
+    let _guard = LOCK.lock();
+    let path = manifest_path(dir);
+    let mut file = OpenOptions::new().create(true).append(true).open(&path)?;
+    file.write_all(format!("{}\n", line).as_bytes())?;
+    Ok(())
+}
+
+/// read the entries of manifest, the newest entry of an archive is used.
+/// return (entries, removed archives)
+pub fn read(dir: &str) -> Result<(BTreeMap<String, ManifestEntry>, Vec<String>), LogError> {
+    let mut entries = BTreeMap::new();
+    let mut removed = vec![];
+    let file = match File::open(manifest_path(dir)) {
+        Ok(v) => v,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok((entries, removed)),
+        Err(e) => return Err(e.into()),
+    };
+    for line in BufReader::new(file).lines() {
+        let line = line?;
+        if let Some(name) = json_str(&line, "removed") {
+            entries.retain(|_, x| x.archive != name);
+            removed.push(name);
+        } else if let Some(entry) = ManifestEntry::from_json(&line) {
+            removed.retain(|x| x != &entry.archive);
+            //the bundle is appended, its members are in the newest archive
+            for x in entries.values_mut().filter(|x| x.archive == entry.archive) {
+                x.size = entry.size;
+                x.sha256 = entry.sha256.clone();
+            }
+            entries.insert(entry.key(), entry);
+        }
+    }
+    Ok((entries, removed))
+}
+
+/// verify the archives of dir by the manifest, detect the missing or corrupted archives
+pub fn verify(dir: &str) -> Result<VerifyReport, LogError> {
+    let (entries, _) = read(dir)?;
+    let mut report = VerifyReport::default();
+    let mut checked = BTreeSet::new();
+    for entry in entries.into_values() {
+        //the members of bundle share the archive
+        if !checked.insert(entry.archive.clone()) {
+            continue;
+        }
+        let name = entry.archive;
+        let path = join(dir, &name);
+        match std::fs::metadata(&path) {
+            Err(_) => report.missing.push(name),
+            Ok(m) => {
+                if m.len() != entry.size || sha256_file(&path)? != entry.sha256 {
+                    report.corrupted.push(name);
+                } else {
+                    report.ok.push(name);
+                }
+            }
+        }
+    }
+    Ok(report)
+}
+
+/// the sha256 hex of file
+pub fn sha256_file(path: &str) -> Result<String, LogError> {
+    let mut file = File::open(path)?;
+    let mut hasher = Sha256::new();
+    let mut buf = vec![0u8; 64 * 1024];
+    loop {
+        let len = file.read(&mut buf)?;
+        if len == 0 {
+            break;
+        }
+        hasher.update(&buf[..len]);
+    }
+    Ok(hasher
+        .finalize()
+        .iter()
+        .map(|x| format!("{:02x}", x))
+        .collect())
+}
+
+/// rewrite the manifest of dir with the entries changed by f
+fn rewrite(
+    dir: &str,
+    f: impl FnOnce(&mut BTreeMap<String, ManifestEntry>),
+) -> Result<(), LogError> {
+    let _guard = LOCK.lock();
+    let (mut entries, _) = read(dir)?;
+    f(&mut entries);
+    let path = manifest_path(dir);
+    let tmp_path = format!("{}.tmp", path);
+    let mut file = File::create(&tmp_path)?;
+    for x in entries.values() {
+        file.write_all(format!("{}\n", x.to_json()).as_bytes())?;
+    }
+    file.sync_all()?;
+    std::fs::rename(&tmp_path, &path)?;
+    Ok(())
+}
+
+fn rename(entries: &mut BTreeMap<String, ManifestEntry>, from: &str, to: &str) {
+    let renamed: Vec<String> = entries
+        .iter()
+        .filter(|(_, x)| x.archive == from)
+        .map(|(k, _)| k.clone())
+        .collect();
+    for key in renamed {
+        if let Some(mut entry) = entries.remove(&key) {
+            entry.archive = to.to_string();
+            entries.insert(entry.key(), entry);
+        }
+    }
+}
+
+fn manifest_path(dir: &str) -> String {
+    join(dir, MANIFEST_NAME)
+}
+
+fn join(dir: &str, name: &str) -> String {
+    if dir.is_empty() || dir.ends_with('/') {
+        format!("{}{}", dir, name)
+    } else {
+        format!("{}/{}", dir, name)
+    }
+}
+
+fn relative(dir: &str, path: &str) -> String {
+    let dir = dir.trim_end_matches('/');
+    match path.strip_prefix(dir) {
+        Some(v) if !dir.is_empty() => v.trim_start_matches('/').to_string(),
+        _ => path.to_string(),
+    }
+}
+
+/// (date, level) of FastLogFormat or FastLogFormatJson line
+fn parse_record(line: &str) -> Option<(&str, &str)> {
+    if line.starts_with('{') {
+        let date = json_raw(line, "date")?;
+        let level = json_raw(line, "level")?;
+        return Some((date, level));
+    }
+    //2026-10-18 12:00:00.000000  [INFO] ...
+    let date = line.get(..26)?;
+    if fastdate::DateTime::parse("YYYY-MM-DD hh:mm:ss.000000", date).is_err() {
+        return None;
+    }
+    let rest = &line[26..];
+    let start = rest.find('[')? + 1;
+    let end = start + rest[start..].find(']')?;
+    Some((date, &rest[start..end]))
+}
+
+fn escape(v: &str) -> String {
+    v.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+/// the string value of key, not unescape
+fn json_raw<'a>(line: &'a str, key: &str) -> Option<&'a str> {
+    let pat = format!("\"{}\":\"", key);
+    let start = line.find(&pat)? + pat.len();
+    let bytes = line.as_bytes();
+    let mut end = start;
+    while end < bytes.len() {
+        match bytes[end] {
+            b'\\' => end += 2,
+            b'"' => return Some(&line[start..end]),
+            _ => end += 1,
+        }
+    }
+    None
+}
+
+fn json_str(line: &str, key: &str) -> Option<String> {
+    Some(
+        json_raw(line, key)?
+            .replace("\\\"", "\"")
+            .replace("\\\\", "\\"),
+    )
+}
+
+fn json_num(line: &str, key: &str) -> Option<u64> {
+    let pat = format!("\"{}\":", key);
+    let start = line.find(&pat)? + pat.len();
+    let end = line[start..]
+        .find(|c: char| !c.is_ascii_digit())
+        .map(|x| start + x)
+        .unwrap_or(line.len());
+    line[start..end].parse().ok()
+}
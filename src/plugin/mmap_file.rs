@@ -0,0 +1,217 @@
+use crate::error::{report, LogError, ReportError};
+use crate::plugin::file_split::SplitFile;
+use memmap2::MmapMut;
+use std::cell::RefCell;
+use std::fs::{File, OpenOptions};
+use std::io::{Read, Seek, SeekFrom};
+
+/// the min size of the mapped region, when the rolling has no size limit
+pub const MMAP_MIN_SIZE: usize = 1024 * 1024;
+
+/// you need enable fast_log = { ... ,features=["mmap"]}
+/// an SplitFile write by memcpy into an memory mapped region, no syscall for each write.
+/// the file is preallocated to the BySize limit of the rolling(or grown by doubling), the written length is tracked in memory,
+/// and the file is trimmed to the written length before rolling and on exit.
+/// the unwritten region is zero bytes, so the written length is recovered after an crash.
+/// copytruncate(for example logrotate) is NOT supported, an write after the file is truncated by others may raise SIGBUS.
+/// the file length is only checked on flush(the tick), use an rename rotation with `fast_log::reopen()` instead. for example:
+/// ```rust,ignore
+/// use fast_log::Config;
+/// use fast_log::consts::LogSize;
+/// use fast_log::plugin::file_split::{KeepType, Rolling, RollingType};
+/// use fast_log::plugin::mmap_file::MmapFile;
+/// use fast_log::plugin::packer::LogPacker;
+/// let config = Config::new().split::<MmapFile, _, _, _>(
+///     "target/logs/temp.log",
+///     KeepType::KeepNum(10),
+///     LogPacker {},
+///     Rolling::new(RollingType::BySize(LogSize::MB(100))),
+/// );
+/// ```
+pub struct MmapFile {
+    inner: RefCell<Inner>,
+}
+
+struct Inner {
+    file: File,
+    path: String,
+    map: Option<MmapMut>,
+    /// the written length
+    len: usize,
+    /// the length preallocated when mapped
+    reserve: usize,
+}
+
+impl Inner {
+    fn cap(&self) -> usize {
+        self.map.as_ref().map(|m| m.len()).unwrap_or(0)
+    }
+
+    /// map the file with the capacity at least cap
+    fn grow(&mut self, cap: usize) -> std::io::Result<()> {
+        if self.cap() >= cap {
+            return Ok(());
+        }
+        self.map = None;
+        if self.file.metadata()?.len() < cap as u64 {
+            self.file.set_len(cap as u64)?;
+        }
+        self.map = Some(map_mut(&self.file)?);
+        Ok(())
+    }
+
+    /// unmap if the file is truncated by others, an memcpy beyond the end of file raise SIGBUS
+    fn check_truncated(&mut self) -> std::io::Result<()> {
+        if self.map.is_none() {
+            return Ok(());
+        }
+        let file_len = self.file.metadata()?.len() as usize;
+        if file_len < self.cap() {
+            report(ReportError::new(
+                "MmapFile",
+                "flush",
+                &self.path,
+                "the mapped file is truncated by others".to_string(),
+            ));
+            self.map = None;
+            self.len = self.len.min(file_len);
+        }
+        Ok(())
+    }
+
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        let end = self.len + buf.len();
+        if end > self.cap() {
+            let cap = end.max(self.cap() * 2).max(self.reserve).max(MMAP_MIN_SIZE);
+            self.grow(cap)?;
+        }
+        if let Some(map) = &mut self.map {
+            map[self.len..end].copy_from_slice(buf);
+        }
+        self.len = end;
+        Ok(buf.len())
+    }
+
+    /// unmap and set the file length to the written length
+    fn trim(&mut self) -> std::io::Result<()> {
+        self.map = None;
+        self.file.set_len(self.len as u64)
+    }
+}
+
+#[allow(unsafe_code)]
+fn map_mut(file: &File) -> std::io::Result<MmapMut> {
+    //the file is owned by MmapFile, and only resized by MmapFile after unmapped
+    unsafe { MmapMut::map_mut(file) }
+}
+
+/// the length without the zero bytes at the end(the unwritten region left by an crash)
+fn written_len(file: &mut File) -> std::io::Result<usize> {
+    let mut end = file.metadata()?.len();
+    let mut buf = vec![0u8; 64 * 1024];
+    while end > 0 {
+        let start = end.saturating_sub(buf.len() as u64);
+        let chunk = &mut buf[..(end - start) as usize];
+        file.seek(SeekFrom::Start(start))?;
+        file.read_exact(chunk)?;
+        if let Some(idx) = chunk.iter().rposition(|x| *x != 0) {
+            return Ok(start as usize + idx + 1);
+        }
+        end = start;
+    }
+    Ok(0)
+}
+
+impl SplitFile for MmapFile {
+    fn new(path: &str) -> Result<Self, LogError>
+    where
+        Self: Sized,
+    {
+        let mut file = OpenOptions::new()
+            .create(true)
+            .read(true)
+            .write(true)
+            .truncate(false)
+            .open(path)?;
+        let len = written_len(&mut file)?;
+        Ok(Self {
+            inner: RefCell::new(Inner {
+                file,
+                path: path.to_string(),
+                map: None,
+                len,
+                reserve: 0,
+            }),
+        })
+    }
+
+    fn seek(&self, pos: SeekFrom) -> std::io::Result<u64> {
+        let mut inner = self.inner.borrow_mut();
+        let new = match pos {
+            SeekFrom::Start(v) => v as i64,
+            SeekFrom::End(v) | SeekFrom::Current(v) => inner.len as i64 + v,
+        };
+        if new < 0 {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                "invalid seek to a negative position",
+            ));
+        }
+        if new as usize > inner.cap() {
+            inner.grow(new as usize)?;
+        }
+        inner.len = new as usize;
+        Ok(new as u64)
+    }
+
+    fn write(&self, buf: &[u8]) -> std::io::Result<usize> {
+        self.inner.borrow_mut().write(buf)
+    }
+
+    fn truncate(&self) -> std::io::Result<()> {
+        let mut inner = self.inner.borrow_mut();
+        inner.len = 0;
+        inner.map = None;
+        inner.file.set_len(0)?;
+        //preallocate again
+        let reserve = inner.reserve;
+        inner.grow(reserve)
+    }
+
+    fn flush(&self) {
+        //truncated by others, remap before the next write
+        let _ = self.inner.borrow_mut().check_truncated();
+    }
+
+    fn len(&self) -> usize {
+        self.inner.borrow().len
+    }
+
+    fn offset(&self) -> usize {
+        self.len().saturating_sub(1)
+    }
+
+    fn sync(&self) -> std::io::Result<()> {
+        let inner = self.inner.borrow();
+        if let (Some(map), true) = (&inner.map, inner.len > 0) {
+            map.flush_range(0, inner.len)?;
+        }
+        inner.file.sync_data()
+    }
+
+    fn reserve(&self, len: usize) -> std::io::Result<()> {
+        let mut inner = self.inner.borrow_mut();
+        inner.reserve = len;
+        inner.grow(len)
+    }
+
+    fn trim(&self) -> std::io::Result<()> {
+        self.inner.borrow_mut().trim()
+    }
+}
+
+impl Drop for MmapFile {
+    fn drop(&mut self) {
+        let _ = self.inner.get_mut().trim();
+    }
+}
@@ -1,6 +1,14 @@
+pub mod compress_file;
 pub mod console;
+pub mod durability;
 pub mod file;
 pub mod file_loop;
 pub mod file_name;
 pub mod file_split;
+#[cfg(feature = "manifest")]
+pub mod manifest;
+#[cfg(feature = "mmap")]
+pub mod mmap_file;
+pub mod pack_pool;
 pub mod packer;
+pub mod schedule;
@@ -0,0 +1,129 @@
+use crate::{chan, spawn, Sender};
+use parking_lot::{Condvar, Mutex};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// what to do when the pending packs of PackPool are full
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Default)]
+pub enum FullPolicy {
+    /// wait until an pack is done, the appender(and the log channel) is blocked
+    #[default]
+    Block,
+    /// roll the log file but not pack it, the log file is kept as an archive
+    KeepUncompressed,
+    /// roll the log file and remove it
+    Discard,
+}
+
+type Job = Box<dyn FnOnce() + Send>;
+
+struct PoolInner {
+    sender: Sender<Job>,
+    pending: Mutex<usize>,
+    done: Condvar,
+}
+
+/// an bounded pool of pack workers, can be shared by many FileSplitAppenders.
+/// the appender check the pending packs before rolling, and use `full_policy` when it is full.
+/// for example:
+/// ```rust
+/// use fast_log::Config;
+/// use fast_log::consts::LogSize;
+/// use fast_log::plugin::file_split::{KeepType, Rolling, RollingType};
+/// use fast_log::plugin::pack_pool::{FullPolicy, PackPool};
+/// use fast_log::plugin::packer::LogPacker;
+/// let pool = PackPool::new(2, 8).full_policy(FullPolicy::KeepUncompressed);
+/// let config = Config::new().pack_pool(pool).file_split(
+///     "target/logs/",
+///     Rolling::new(RollingType::BySize(LogSize::MB(1))),
+///     KeepType::KeepNum(10),
+///     LogPacker {},
+/// );
+/// ```
+#[derive(Clone)]
+pub struct PackPool {
+    /// the number of worker threads
+    pub concurrency: usize,
+    /// the max number of queued and running packs
+    pub max_pending: usize,
+    pub full_policy: FullPolicy,
+    inner: Arc<PoolInner>,
+}
+
+impl PackPool {
+    pub fn new(concurrency: usize, max_pending: usize) -> Self {
+        let concurrency = concurrency.max(1);
+        let (sender, receiver) = chan::<Job>(None);
+        for _ in 0..concurrency {
+            let receiver = receiver.clone();
+            spawn(move || {
+                //exit when all PackPool are dropped
+                while let Ok(job) = receiver.recv() {
+                    job();
+                }
+            });
+        }
+        Self {
+            concurrency,
+            max_pending: max_pending.max(1),
+            full_policy: FullPolicy::default(),
+            inner: Arc::new(PoolInner {
+                sender,
+                pending: Mutex::new(0),
+                done: Condvar::new(),
+            }),
+        }
+    }
+
+    /// set what to do when full, default FullPolicy::Block
+    pub fn full_policy(mut self, policy: FullPolicy) -> Self {
+        self.full_policy = policy;
+        self
+    }
+
+    /// the number of queued and running packs
+    pub fn pending(&self) -> usize {
+        *self.inner.pending.lock()
+    }
+
+    /// is pending + queued(not submitted yet) reached max_pending
+    pub fn is_full(&self, queued: usize) -> bool {
+        self.pending() + queued >= self.max_pending
+    }
+
+    /// block until not full, `queued` return the packs not submitted yet
+    pub fn wait(&self, queued: impl Fn() -> usize) {
+        let mut pending = self.inner.pending.lock();
+        while *pending + queued() >= self.max_pending {
+            //the queued packs are submitted without notify, so check it again later
+            self.inner
+                .done
+                .wait_for(&mut pending, Duration::from_millis(10));
+        }
+    }
+
+    /// run the job on an worker
+    pub fn submit<F: FnOnce() + Send + 'static>(&self, job: F) {
+        *self.inner.pending.lock() += 1;
+        let inner = self.inner.clone();
+        let job = Box::new(move || {
+            job();
+            *inner.pending.lock() -= 1;
+            inner.done.notify_all();
+        });
+        if let Err(e) = self.inner.sender.send(job) {
+            //never happen, the workers hold the receiver
+            (e.0)();
+        }
+    }
+
+    /// run the job on an worker and wait it done
+    pub fn submit_wait<F: FnOnce() + Send + 'static>(&self, job: F) {
+        let (sender, receiver) = chan::<()>(Some(1));
+        self.submit(move || {
+            job();
+            let _ = sender.send(());
+        });
+        let _ = receiver.recv();
+    }
+}
@@ -1,4 +1,12 @@
 use crate::error::LogError;
+#[cfg(any(
+    feature = "zip",
+    feature = "lz4",
+    feature = "gzip",
+    feature = "zstd",
+    feature = "encrypt"
+))]
+use crate::plugin::file_split::pack_path;
 use crate::plugin::file_split::Packer;
 use std::fs::File;
 
@@ -20,11 +28,108 @@ impl Packer for LogPacker {
 use zip::result::ZipResult;
 #[cfg(feature = "zip")]
 use zip::write::FileOptions;
+#[cfg(feature = "zip")]
+pub use zip::CompressionMethod;
 
 /// you need enable fast_log = { ... ,features=["zip"]}
 /// the zip compress
 #[cfg(feature = "zip")]
-pub struct ZipPacker {}
+#[derive(Clone, Debug)]
+pub struct ZipPacker {
+    /// default CompressionMethod::Deflated
+    pub method: CompressionMethod,
+    /// compression level, None is the default level of method
+    pub level: Option<i64>,
+    /// Some(date format): append the log files of one date into one zip, for example temp2026-10-18.zip
+    pub bundle: Option<String>,
+}
+
+#[cfg(feature = "zip")]
+impl ZipPacker {
+    pub fn new() -> Self {
+        Self {
+            method: CompressionMethod::Deflated,
+            level: None,
+            bundle: None,
+        }
+    }
+
+    /// set compression method
+    pub fn method(mut self, method: CompressionMethod) -> Self {
+        self.method = method;
+        self
+    }
+
+    /// set compression level, for example 0..=9 of Deflated
+    pub fn level(mut self, level: i64) -> Self {
+        self.level = Some(level);
+        self
+    }
+
+    /// append the log files of one day into one zip, for example temp2026-10-18.zip
+    pub fn bundle_daily(mut self) -> Self {
+        self.bundle = Some("YYYY-MM-DD".to_string());
+        self
+    }
+
+    fn options(&self) -> FileOptions<'static, ()> {
+        FileOptions::default()
+            .compression_method(self.method)
+            .compression_level(self.level)
+    }
+
+    /// append the log file into the bundle zip of its date.
+    /// the zip is appended in an copy(temp2026-10-18.zip.tmp) then renamed, an crash never corrupt it
+    fn do_bundle(&self, mut log_file: File, log_file_path: &str) -> Result<bool, LogError> {
+        static LOCK: std::sync::Mutex<()> = std::sync::Mutex::new(());
+        let log_name = match log_file_path.rfind('/') {
+            Some(v) => &log_file_path[v + 1..],
+            None => log_file_path,
+        };
+        let zip_path = self.archive_path(log_file_path);
+        let tmp_path = format!("{}.tmp", zip_path);
+        let _guard = LOCK.lock();
+        let exists = std::path::Path::new(&zip_path).exists();
+        if exists {
+            let zip_file = File::open(&zip_path)
+                .map_err(|e| LogError::from(format!("[fast_log] open(&{}) fail:{}", zip_path, e)))?;
+            let archive = zip::ZipArchive::new(zip_file).map_err(|e| LogError::from(e.to_string()))?;
+            if archive.index_for_name(log_name).is_some() {
+                //appended by an retry(or before an crash), the log file can be removed
+                return Ok(true);
+            }
+            std::fs::copy(&zip_path, &tmp_path)?;
+        }
+        let zip_file = std::fs::OpenOptions::new()
+            .create(true)
+            .read(true)
+            .write(true)
+            .truncate(!exists)
+            .open(&tmp_path)
+            .map_err(|e| LogError::from(format!("[fast_log] open(&{}) fail:{}", tmp_path, e)))?;
+        let mut zip = if exists {
+            zip::ZipWriter::new_append(zip_file).map_err(|e| LogError::from(e.to_string()))?
+        } else {
+            zip::ZipWriter::new(zip_file)
+        };
+        zip.start_file::<&str, ()>(log_name, self.options())
+            .map_err(|e| LogError::from(e.to_string()))?;
+        std::io::copy(&mut log_file, &mut zip).map_err(|e| LogError::from(e.to_string()))?;
+        let zip_file = zip
+            .finish()
+            .map_err(|e| LogError::from(format!("[fast_log] try zip fail{:?}", e)))?;
+        zip_file.sync_all()?;
+        std::fs::rename(&tmp_path, &zip_path)?;
+        Ok(true)
+    }
+}
+
+#[cfg(feature = "zip")]
+impl Default for ZipPacker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
 
 #[cfg(feature = "zip")]
 impl Packer for ZipPacker {
@@ -32,18 +137,39 @@ impl Packer for ZipPacker {
         "zip"
     }
 
+    fn bundle_format(&self) -> Option<&str> {
+        self.bundle.as_deref()
+    }
+
+    fn archive_path(&self, log_file_path: &str) -> String {
+        match &self.bundle {
+            None => pack_path(log_file_path, self.pack_name()),
+            Some(format) => {
+                let (dir, log_name) = match log_file_path.rfind('/') {
+                    Some(v) => (&log_file_path[..v + 1], &log_file_path[v + 1..]),
+                    None => ("", log_file_path),
+                };
+                let (base, date) = bundle_date(log_name, format);
+                format!("{}{}{}.{}", dir, base, date, self.pack_name())
+            }
+        }
+    }
+
     fn do_pack(&self, mut log_file: File, log_file_path: &str) -> Result<bool, LogError> {
         use std::io::Write;
+        if self.bundle.is_some() {
+            return self.do_bundle(log_file, log_file_path);
+        }
         let mut log_name = log_file_path.replace("\\", "/").to_string();
         if let Some(v) = log_file_path.rfind("/") {
             log_name = log_name[(v + 1)..log_name.len()].to_string();
         }
-        let zip_path = log_file_path.replace(".log", ".zip");
+        let zip_path = pack_path(log_file_path, self.pack_name());
         let zip_file = File::create(&zip_path)
             .map_err(|e| LogError::from(format!("[fast_log] create(&{}) fail:{}", zip_path, e)))?;
         //write zip bytes data
         let mut zip = zip::ZipWriter::new(zip_file);
-        zip.start_file::<String, ()>(log_name, FileOptions::default())
+        zip.start_file::<String, ()>(log_name, self.options())
             .map_err(|e| LogError::from(e.to_string()))?;
         //buf reader
         std::io::copy(&mut log_file, &mut zip).map_err(|e| LogError::from(e.to_string()))?;
@@ -60,13 +186,73 @@ impl Packer for ZipPacker {
     }
 }
 
+/// split the log file name to (base, date) of the bundle,
+/// for example temp2026-10-18T12-00-00.000000.log => (temp, 2026-10-18), temp.log.1 => (temp, today)
+#[cfg(feature = "zip")]
+fn bundle_date(log_name: &str, format: &str) -> (String, String) {
+    let bytes = log_name.as_bytes();
+    for idx in 0..bytes.len().saturating_sub(9) {
+        let v = &bytes[idx..idx + 10];
+        let is_date = v.iter().enumerate().all(|(i, b)| match i {
+            4 | 7 => *b == b'-',
+            _ => b.is_ascii_digit(),
+        });
+        if is_date {
+            if let Ok(time) = fastdate::DateTime::parse("YYYY-MM-DD", &log_name[idx..idx + 10]) {
+                return (log_name[..idx].to_string(), time.format(format));
+            }
+        }
+    }
+    let base = match log_name.find('.') {
+        Some(idx) => &log_name[..idx],
+        None => log_name,
+    };
+    let today = fastdate::DateTime::now().set_offset(fastdate::offset_sec());
+    (base.to_string(), today.format(format))
+}
+
 /// you need enable fast_log = { ... ,features=["lz4"]}
 #[cfg(feature = "lz4")]
 use lz4_flex::frame::FrameEncoder;
 
-/// the zip compress
+/// the lz4 compress
 #[cfg(feature = "lz4")]
-pub struct LZ4Packer {}
+#[derive(Clone, Debug)]
+pub struct LZ4Packer {
+    /// the frame block size, default BlockSize::Auto
+    pub block_size: lz4_flex::frame::BlockSize,
+    /// write the checksum of content, default false
+    pub content_checksum: bool,
+}
+
+#[cfg(feature = "lz4")]
+impl LZ4Packer {
+    pub fn new() -> Self {
+        Self {
+            block_size: lz4_flex::frame::BlockSize::Auto,
+            content_checksum: false,
+        }
+    }
+
+    /// set the frame block size, bigger block is better ratio
+    pub fn block_size(mut self, block_size: lz4_flex::frame::BlockSize) -> Self {
+        self.block_size = block_size;
+        self
+    }
+
+    /// write the checksum of content
+    pub fn content_checksum(mut self, enable: bool) -> Self {
+        self.content_checksum = enable;
+        self
+    }
+}
+
+#[cfg(feature = "lz4")]
+impl Default for LZ4Packer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
 
 #[cfg(feature = "lz4")]
 impl Packer for LZ4Packer {
@@ -75,11 +261,14 @@ impl Packer for LZ4Packer {
     }
 
     fn do_pack(&self, mut log_file: File, log_file_path: &str) -> Result<bool, LogError> {
-        let lz4_path = log_file_path.replace(".log", ".lz4");
+        let lz4_path = pack_path(log_file_path, self.pack_name());
         let lz4_file = File::create(&lz4_path)
             .map_err(|e| LogError::from(format!("[fast_log] create(&{}) fail:{}", lz4_path, e)))?;
         //write lz4 bytes data
-        let mut encoder = FrameEncoder::new(lz4_file);
+        let info = lz4_flex::frame::FrameInfo::new()
+            .block_size(self.block_size)
+            .content_checksum(self.content_checksum);
+        let mut encoder = FrameEncoder::with_frame_info(info, lz4_file);
         //buf reader
         std::io::copy(&mut log_file, &mut encoder).map_err(|e| LogError::from(e.to_string()))?;
         let result = encoder.finish();
@@ -98,8 +287,35 @@ use flate2::write::GzEncoder;
 #[cfg(feature = "gzip")]
 use flate2::Compression;
 
+/// the gzip compress
+#[cfg(feature = "gzip")]
+#[derive(Clone, Debug)]
+pub struct GZipPacker {
+    /// compression level 0..=9, default 6
+    pub level: u32,
+}
+
+#[cfg(feature = "gzip")]
+impl GZipPacker {
+    pub fn new() -> Self {
+        Self {
+            level: Compression::default().level(),
+        }
+    }
+
+    /// set compression level 0..=9
+    pub fn level(mut self, level: u32) -> Self {
+        self.level = level;
+        self
+    }
+}
+
 #[cfg(feature = "gzip")]
-pub struct GZipPacker {}
+impl Default for GZipPacker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
 
 #[cfg(feature = "gzip")]
 impl Packer for GZipPacker {
@@ -109,11 +325,11 @@ impl Packer for GZipPacker {
 
     fn do_pack(&self, mut log_file: File, log_file_path: &str) -> Result<bool, LogError> {
         use std::io::Write;
-        let zip_path = log_file_path.replace(".log", ".gz");
+        let zip_path = pack_path(log_file_path, self.pack_name());
         let zip_file = File::create(&zip_path)
             .map_err(|e| LogError::from(format!("[fast_log] create(&{}) fail:{}", zip_path, e)))?;
         //write zip bytes data
-        let mut zip = GzEncoder::new(zip_file, Compression::default());
+        let mut zip = GzEncoder::new(zip_file, Compression::new(self.level));
         std::io::copy(&mut log_file, &mut zip).map_err(|e| LogError::from(e.to_string()))?;
         zip.flush().map_err(|e| LogError::from(e.to_string()))?;
         let finish = zip.finish();
@@ -126,3 +342,368 @@ impl Packer for GZipPacker {
         return Ok(true);
     }
 }
+
+/// you need enable fast_log = { ... ,features=["zstd"]}
+/// the zstd compress
+#[cfg(feature = "zstd")]
+#[derive(Clone, Debug)]
+pub struct ZstdPacker {
+    /// compression level 1..=22, 0 is the zstd default(3)
+    pub level: i32,
+    /// long distance matching, better ratio for large log files with repeated content
+    pub long_distance_matching: bool,
+}
+
+#[cfg(feature = "zstd")]
+impl ZstdPacker {
+    pub fn new() -> Self {
+        Self {
+            level: 0,
+            long_distance_matching: false,
+        }
+    }
+
+    /// set compression level 1..=22
+    pub fn level(mut self, level: i32) -> Self {
+        self.level = level;
+        self
+    }
+
+    /// enable long distance matching
+    pub fn long_distance_matching(mut self, enable: bool) -> Self {
+        self.long_distance_matching = enable;
+        self
+    }
+}
+
+#[cfg(feature = "zstd")]
+impl Default for ZstdPacker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(feature = "zstd")]
+impl Packer for ZstdPacker {
+    fn pack_name(&self) -> &'static str {
+        "zst"
+    }
+
+    fn do_pack(&self, mut log_file: File, log_file_path: &str) -> Result<bool, LogError> {
+        let zstd_path = pack_path(log_file_path, self.pack_name());
+        let zstd_file = File::create(&zstd_path)
+            .map_err(|e| LogError::from(format!("[fast_log] create(&{}) fail:{}", zstd_path, e)))?;
+        //write zstd bytes data
+        let mut encoder = zstd::stream::Encoder::new(zstd_file, self.level)
+            .map_err(|e| LogError::from(e.to_string()))?;
+        encoder
+            .long_distance_matching(self.long_distance_matching)
+            .map_err(|e| LogError::from(e.to_string()))?;
+        std::io::copy(&mut log_file, &mut encoder).map_err(|e| LogError::from(e.to_string()))?;
+        let finish = encoder.finish();
+        if finish.is_err() {
+            return Err(LogError::from(format!(
+                "[fast_log] try zstd fail{:?}",
+                finish.err()
+            )));
+        }
+        Ok(true)
+    }
+}
+
+/// you need enable fast_log = { ... ,features=["encrypt"]}
+/// encrypt the log file with AES-256-GCM, optionally after an compress packer.
+/// the file is encrypted by chunks, every chunk is authenticated and the last chunk is marked,
+/// so an modified or truncated archive fail to decrypt. use `decrypt_file` to read it back.
+/// for example:
+/// ```rust,ignore
+/// use fast_log::plugin::packer::{EncryptPacker, GZipPacker};
+/// let key = [7u8; 32];
+/// // temp2026-10-18T12-00-00.000000.gz.enc
+/// let packer = EncryptPacker::new(key).after(GZipPacker::new());
+/// ```
+#[cfg(feature = "encrypt")]
+pub struct EncryptPacker {
+    key: [u8; 32],
+    /// the compress packer run before encrypt
+    pub inner: Option<Box<dyn Packer>>,
+    /// the plain bytes of one chunk, default 64KB
+    pub chunk_size: usize,
+    pack_name: String,
+}
+
+#[cfg(feature = "encrypt")]
+const ENCRYPT_MAGIC: &[u8; 4] = b"FLE1";
+
+/// the max plain bytes of one chunk, the chunk_size of an archive is not trusted before decrypted
+#[cfg(feature = "encrypt")]
+pub const MAX_CHUNK_SIZE: usize = 16 * 1024 * 1024;
+
+#[cfg(feature = "encrypt")]
+impl EncryptPacker {
+    pub fn new(key: [u8; 32]) -> Self {
+        Self {
+            key,
+            inner: None,
+            chunk_size: 64 * 1024,
+            pack_name: "log.enc".to_string(),
+        }
+    }
+
+    /// the key is 64 hex chars
+    pub fn from_hex(key: &str) -> Result<Self, LogError> {
+        Ok(Self::new(parse_key(key)?))
+    }
+
+    /// encrypt the output of an compress packer, for example GZipPacker => temp.gz.enc.
+    /// an bundle(`ZipPacker::new().bundle_daily()`) is decrypted to append and encrypted again
+    pub fn after<P: Packer + 'static>(mut self, packer: P) -> Self {
+        self.pack_name = format!("{}.enc", packer.pack_name());
+        self.inner = Some(Box::new(packer));
+        self
+    }
+
+    /// set the plain bytes of one chunk, 1..=MAX_CHUNK_SIZE
+    pub fn chunk_size(mut self, chunk_size: usize) -> Self {
+        self.chunk_size = chunk_size.clamp(1, MAX_CHUNK_SIZE);
+        self
+    }
+}
+
+#[cfg(feature = "encrypt")]
+impl Packer for EncryptPacker {
+    fn pack_name(&self) -> &str {
+        &self.pack_name
+    }
+
+    fn bundle_format(&self) -> Option<&str> {
+        self.inner.as_ref().and_then(|x| x.bundle_format())
+    }
+
+    fn archive_path(&self, log_file_path: &str) -> String {
+        match &self.inner {
+            None => pack_path(log_file_path, &self.pack_name),
+            Some(inner) => format!("{}.enc", inner.archive_path(log_file_path)),
+        }
+    }
+
+    fn do_pack(&self, log_file: File, log_file_path: &str) -> Result<bool, LogError> {
+        let enc_path = self.archive_path(log_file_path);
+        let (plain_path, plain_file) = match &self.inner {
+            None => (log_file_path.to_string(), log_file),
+            Some(inner) => {
+                let path = inner.archive_path(log_file_path);
+                //the bundle is appended in plain, then encrypted again
+                if inner.bundle_format().is_some() && std::path::Path::new(&enc_path).exists() {
+                    decrypt_file(&self.key, &enc_path, &path)?;
+                }
+                inner.do_pack(log_file, log_file_path)?;
+                let file = File::open(&path)
+                    .map_err(|e| LogError::from(format!("[fast_log] open(&{}) fail:{}", path, e)))?;
+                (path, file)
+            }
+        };
+        let enc_file = File::create(&enc_path)
+            .map_err(|e| LogError::from(format!("[fast_log] create(&{}) fail:{}", enc_path, e)))?;
+        encrypt(&self.key, self.chunk_size, plain_file, enc_file)?;
+        if plain_path != log_file_path {
+            std::fs::remove_file(&plain_path)?;
+        }
+        Ok(true)
+    }
+}
+
+/// encrypt reader into writer, format: magic,chunk_size(u32),nonce prefix(8),[len(u32),chunk]...
+#[cfg(feature = "encrypt")]
+pub fn encrypt<R: std::io::Read, W: std::io::Write>(
+    key: &[u8; 32],
+    chunk_size: usize,
+    mut reader: R,
+    writer: W,
+) -> Result<(), LogError> {
+    use aes_gcm::aead::rand_core::RngCore;
+    use aes_gcm::aead::OsRng;
+    use std::io::Write;
+    let mut writer = std::io::BufWriter::new(writer);
+    let cipher = new_cipher(key);
+    let mut prefix = [0u8; 8];
+    OsRng.fill_bytes(&mut prefix);
+    writer.write_all(ENCRYPT_MAGIC)?;
+    writer.write_all(&(chunk_size as u32).to_le_bytes())?;
+    writer.write_all(&prefix)?;
+    let mut chunk = vec![0u8; chunk_size];
+    let mut len = read_full(&mut reader, &mut chunk)?;
+    let mut counter = 0u32;
+    loop {
+        let mut next = vec![0u8; chunk_size];
+        let next_len = if len == chunk_size {
+            read_full(&mut reader, &mut next)?
+        } else {
+            0
+        };
+        let last = next_len == 0;
+        let data = seal(&cipher, &prefix, counter, last, &chunk[..len])?;
+        writer.write_all(&(data.len() as u32).to_le_bytes())?;
+        writer.write_all(&data)?;
+        if last {
+            break;
+        }
+        counter = counter
+            .checked_add(1)
+            .ok_or_else(|| LogError::from("[fast_log] encrypt too many chunks"))?;
+        chunk = next;
+        len = next_len;
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+/// decrypt an archive of EncryptPacker.
+/// the chunks are written once authenticated, the writer may get the chunks before an modified one,
+/// use `decrypt_file` to get all or nothing
+#[cfg(feature = "encrypt")]
+pub fn decrypt<R: std::io::Read, W: std::io::Write>(
+    key: &[u8; 32],
+    reader: R,
+    mut writer: W,
+) -> Result<(), LogError> {
+    use aes_gcm::aead::{Aead, Payload};
+    let mut reader = std::io::BufReader::new(reader);
+    let cipher = new_cipher(key);
+    let mut head = [0u8; 16];
+    if read_full(&mut reader, &mut head)? != head.len() || &head[..4] != ENCRYPT_MAGIC {
+        return Err(LogError::from("[fast_log] not an encrypted log archive"));
+    }
+    let chunk_size = u32::from_le_bytes([head[4], head[5], head[6], head[7]]) as usize;
+    if chunk_size == 0 || chunk_size > MAX_CHUNK_SIZE {
+        return Err(LogError::from("[fast_log] bad encrypted chunk size"));
+    }
+    let prefix: [u8; 8] = head[8..16].try_into().unwrap_or_default();
+    let mut counter = 0u32;
+    loop {
+        let mut len = [0u8; 4];
+        if read_full(&mut reader, &mut len)? != len.len() {
+            return Err(LogError::from("[fast_log] encrypted log archive is truncated"));
+        }
+        let len = u32::from_le_bytes(len) as usize;
+        //plain chunk + 16 bytes tag
+        if len > chunk_size + 16 {
+            return Err(LogError::from("[fast_log] bad encrypted chunk"));
+        }
+        let mut data = vec![0u8; len];
+        if read_full(&mut reader, &mut data)? != len {
+            return Err(LogError::from("[fast_log] encrypted log archive is truncated"));
+        }
+        let mut last = false;
+        let plain = match cipher.decrypt(
+            &nonce(&prefix, counter),
+            Payload {
+                msg: &data,
+                aad: &[0],
+            },
+        ) {
+            Ok(v) => v,
+            Err(_) => {
+                last = true;
+                cipher
+                    .decrypt(
+                        &nonce(&prefix, counter),
+                        Payload {
+                            msg: &data,
+                            aad: &[1],
+                        },
+                    )
+                    .map_err(|_| LogError::from("[fast_log] decrypt fail, wrong key or modified"))?
+            }
+        };
+        writer.write_all(&plain)?;
+        if last {
+            if read_full(&mut reader, &mut [0u8; 1])? != 0 {
+                return Err(LogError::from("[fast_log] bad data after the last chunk"));
+            }
+            break;
+        }
+        counter = counter.wrapping_add(1);
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+/// decrypt the archive of EncryptPacker into out_path.
+/// it is decrypted into out_path.tmp first, out_path is created only if the whole archive is authenticated
+#[cfg(feature = "encrypt")]
+pub fn decrypt_file(key: &[u8; 32], path: &str, out_path: &str) -> Result<(), LogError> {
+    let file = File::open(path)?;
+    let tmp_path = format!("{}.tmp", out_path);
+    let out = File::create(&tmp_path)?;
+    if let Err(e) = decrypt(key, file, &out).and_then(|_| Ok(out.sync_all()?)) {
+        let _ = std::fs::remove_file(&tmp_path);
+        return Err(e);
+    }
+    std::fs::rename(&tmp_path, out_path)?;
+    Ok(())
+}
+
+/// parse an key of 64 hex chars
+#[cfg(feature = "encrypt")]
+pub fn parse_key(key: &str) -> Result<[u8; 32], LogError> {
+    let key = key.trim();
+    if key.len() != 64 || !key.is_ascii() {
+        return Err(LogError::from("[fast_log] the key must be 64 hex chars"));
+    }
+    let mut v = [0u8; 32];
+    for (i, x) in v.iter_mut().enumerate() {
+        *x = u8::from_str_radix(&key[i * 2..i * 2 + 2], 16)
+            .map_err(|_| LogError::from("[fast_log] the key must be 64 hex chars"))?;
+    }
+    Ok(v)
+}
+
+#[cfg(feature = "encrypt")]
+fn new_cipher(key: &[u8; 32]) -> aes_gcm::Aes256Gcm {
+    use aes_gcm::KeyInit;
+    aes_gcm::Aes256Gcm::new(aes_gcm::Key::<aes_gcm::Aes256Gcm>::from_slice(key))
+}
+
+#[cfg(feature = "encrypt")]
+fn nonce(prefix: &[u8; 8], counter: u32) -> aes_gcm::Nonce<aes_gcm::aead::consts::U12> {
+    let mut v = [0u8; 12];
+    v[..8].copy_from_slice(prefix);
+    v[8..].copy_from_slice(&counter.to_be_bytes());
+    aes_gcm::Nonce::clone_from_slice(&v)
+}
+
+/// the last chunk use aad [1], so the truncated archive is detected
+#[cfg(feature = "encrypt")]
+fn seal(
+    cipher: &aes_gcm::Aes256Gcm,
+    prefix: &[u8; 8],
+    counter: u32,
+    last: bool,
+    data: &[u8],
+) -> Result<Vec<u8>, LogError> {
+    use aes_gcm::aead::{Aead, Payload};
+    cipher
+        .encrypt(
+            &nonce(prefix, counter),
+            Payload {
+                msg: data,
+                aad: &[last as u8],
+            },
+        )
+        .map_err(|_| LogError::from("[fast_log] encrypt fail"))
+}
+
+#[cfg(feature = "encrypt")]
+fn read_full<R: std::io::Read>(reader: &mut R, buf: &mut [u8]) -> std::io::Result<usize> {
+    let mut len = 0;
+    while len < buf.len() {
+        match reader.read(&mut buf[len..]) {
+            Ok(0) => break,
+            Ok(n) => len += n,
+            Err(e) if e.kind() == std::io::ErrorKind::Interrupted => {}
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(len)
+}
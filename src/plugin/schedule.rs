@@ -0,0 +1,267 @@
+use crate::error::LogError;
+use crate::TimeType;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// rolling schedule aligned to the wall clock
+pub enum ScheduleType {
+    /// every duration aligned to clock boundaries,
+    /// for example 15 minutes rolls at :00/:15/:30/:45, one day rolls at 00:00
+    Every(Duration),
+    /// cron expression
+    Cron(Cron),
+}
+
+/// an wall clock rolling schedule, the time is Local(default) or Utc.
+/// the local offset is read at every boundary, so it stays correct across DST changes.
+/// for example:
+/// ```rust
+/// use std::time::Duration;
+/// use fast_log::plugin::file_split::{Rolling, RollingType};
+/// use fast_log::plugin::schedule::Schedule;
+/// let every_15_minute = Rolling::new(RollingType::BySchedule(Schedule::every(Duration::from_secs(15 * 60))));
+/// let midnight_utc = Rolling::new(RollingType::BySchedule(Schedule::cron("0 0 * * *").unwrap().utc()));
+/// ```
+pub struct Schedule {
+    pub how: ScheduleType,
+    pub time_type: TimeType,
+    next: Option<SystemTime>,
+}
+
+impl Schedule {
+    /// every duration aligned to clock boundaries
+    pub fn every(duration: Duration) -> Self {
+        Self {
+            how: ScheduleType::Every(duration),
+            time_type: TimeType::Local,
+            next: None,
+        }
+    }
+
+    /// cron expression "minute hour day_of_month month day_of_week",
+    /// support `*`, `5`, `1,2`, `1-5`, `*/15`, `1-30/2`
+    pub fn cron(expr: &str) -> Result<Self, LogError> {
+        Ok(Self {
+            how: ScheduleType::Cron(Cron::parse(expr)?),
+            time_type: TimeType::Local,
+            next: None,
+        })
+    }
+
+    /// use the utc time
+    pub fn utc(mut self) -> Self {
+        self.time_type = TimeType::Utc;
+        self
+    }
+
+    /// use the local time
+    pub fn local(mut self) -> Self {
+        self.time_type = TimeType::Local;
+        self
+    }
+
+    /// forget the next boundary, it will be computed by the next `is_due`
+    pub fn reset(&mut self) {
+        self.next = None;
+    }
+
+    /// is the next boundary after `last` reached at `now`
+    pub fn is_due(&mut self, last: SystemTime, now: SystemTime) -> bool {
+        let next = match self.next {
+            Some(v) => v,
+            None => {
+                let v = self.next_after(last);
+                self.next = Some(v);
+                v
+            }
+        };
+        if now >= next {
+            self.next = Some(self.next_after(now));
+            true
+        } else {
+            false
+        }
+    }
+
+    /// the offset(sec) of the schedule time at `time`
+    pub fn offset_at(&self, time: SystemTime) -> i32 {
+        offset_of(time, &self.time_type)
+    }
+
+    /// the first boundary after `time`
+    pub fn next_after(&self, time: SystemTime) -> SystemTime {
+        let sec = unix_sec(time);
+        let next = match &self.how {
+            ScheduleType::Every(duration) => {
+                let step = (duration.as_secs() as i64).max(1);
+                let local = sec + offset_at(sec, &self.time_type);
+                let mut next_local = (local.div_euclid(step) + 1) * step;
+                loop {
+                    let next = to_utc(next_local, &self.time_type);
+                    if next > sec {
+                        break next;
+                    }
+                    next_local += step;
+                }
+            }
+            ScheduleType::Cron(cron) => {
+                let local = sec + offset_at(sec, &self.time_type);
+                let mut from = (local.div_euclid(60) + 1) * 60;
+                loop {
+                    match cron.next_local(from) {
+                        None => break i64::MAX / 2,
+                        Some(next_local) => {
+                            let next = to_utc(next_local, &self.time_type);
+                            if next > sec {
+                                break next;
+                            }
+                            from = next_local + 60;
+                        }
+                    }
+                }
+            }
+        };
+        UNIX_EPOCH + Duration::from_secs(next.max(0) as u64)
+    }
+}
+
+fn unix_sec(time: SystemTime) -> i64 {
+    match time.duration_since(UNIX_EPOCH) {
+        Ok(v) => v.as_secs() as i64,
+        Err(e) => -(e.duration().as_secs() as i64),
+    }
+}
+
+/// the offset(sec) of time_type at `time`, the local offset follows the daylight saving time
+pub(crate) fn offset_of(time: SystemTime, time_type: &TimeType) -> i32 {
+    offset_at(unix_sec(time), time_type) as i32
+}
+
+fn offset_at(sec: i64, time_type: &TimeType) -> i64 {
+    match time_type {
+        TimeType::Utc => 0,
+        TimeType::Local => fastdate::sys::Timespec { sec, nsec: 0 }.local().tm_utcoff as i64,
+    }
+}
+
+/// local seconds => utc seconds,use the offset at that time
+pub(crate) fn to_utc(local: i64, time_type: &TimeType) -> i64 {
+    let guess = local - offset_at(local, time_type);
+    local - offset_at(guess, time_type)
+}
+
+/// cron expression "minute hour day_of_month month day_of_week"
+#[derive(Clone, Debug)]
+pub struct Cron {
+    minutes: u64,
+    hours: u64,
+    days: u64,
+    months: u64,
+    week_days: u64,
+    any_day: bool,
+    any_week_day: bool,
+}
+
+impl Cron {
+    pub fn parse(expr: &str) -> Result<Self, LogError> {
+        let fields: Vec<&str> = expr.split_whitespace().collect();
+        if fields.len() != 5 {
+            return Err(LogError::from(format!(
+                "[fast_log] cron expr must have 5 fields:{}",
+                expr
+            )));
+        }
+        let mut week_days = parse_field(fields[4], 0, 7)?;
+        //7 is sunday
+        if week_days & (1 << 7) != 0 {
+            week_days |= 1;
+        }
+        Ok(Self {
+            minutes: parse_field(fields[0], 0, 59)?,
+            hours: parse_field(fields[1], 0, 23)?,
+            days: parse_field(fields[2], 1, 31)?,
+            months: parse_field(fields[3], 1, 12)?,
+            week_days,
+            any_day: fields[2] == "*",
+            any_week_day: fields[4] == "*",
+        })
+    }
+
+    fn match_day(&self, day: u32, week_day: u32) -> bool {
+        let day_match = self.days & (1 << day) != 0;
+        let week_day_match = self.week_days & (1 << week_day) != 0;
+        if self.any_day || self.any_week_day {
+            day_match && week_day_match
+        } else {
+            day_match || week_day_match
+        }
+    }
+
+    /// the first matched minute(local seconds) at or after `from`, search 5 years
+    fn next_local(&self, from: i64) -> Option<i64> {
+        let first_day = from.div_euclid(86400);
+        let first_minute = from.rem_euclid(86400) / 60;
+        for day in first_day..first_day + 366 * 5 {
+            let (_, month, month_day) = civil_from_days(day);
+            let week_day = (day + 4).rem_euclid(7) as u32;
+            if self.months & (1 << month) == 0 || !self.match_day(month_day, week_day) {
+                continue;
+            }
+            let start = if day == first_day { first_minute } else { 0 };
+            for minute in start..1440 {
+                if self.hours & (1 << (minute / 60)) != 0 && self.minutes & (1 << (minute % 60)) != 0
+                {
+                    return Some(day * 86400 + minute * 60);
+                }
+            }
+        }
+        None
+    }
+}
+
+fn parse_field(field: &str, min: u32, max: u32) -> Result<u64, LogError> {
+    let err = || LogError::from(format!("[fast_log] bad cron field:{}", field));
+    let mut bits = 0u64;
+    for item in field.split(',') {
+        let (range, step) = match item.split_once('/') {
+            Some((r, s)) => (r, s.parse::<u32>().map_err(|_| err())?),
+            None => (item, 1),
+        };
+        if step == 0 {
+            return Err(err());
+        }
+        let (start, end) = if range == "*" {
+            (min, max)
+        } else if let Some((a, b)) = range.split_once('-') {
+            (
+                a.parse::<u32>().map_err(|_| err())?,
+                b.parse::<u32>().map_err(|_| err())?,
+            )
+        } else {
+            let v = range.parse::<u32>().map_err(|_| err())?;
+            (v, if item.contains('/') { max } else { v })
+        };
+        if start < min || end > max || start > end {
+            return Err(err());
+        }
+        let mut v = start;
+        while v <= end {
+            bits |= 1 << v;
+            v += step;
+        }
+    }
+    Ok(bits)
+}
+
+/// days since 1970-01-01 => (year, month, day)
+fn civil_from_days(z: i64) -> (i64, u32, u32) {
+    let z = z + 719468;
+    let era = z.div_euclid(146097);
+    let doe = z.rem_euclid(146097);
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let d = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let m = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let y = yoe + era * 400 + if m <= 2 { 1 } else { 0 };
+    (y, m, d)
+}
@@ -7,6 +7,8 @@ pub type SendError<T> = crossbeam_channel::SendError<T>;
 #[cfg(feature = "runtime_thread")]
 pub type RecvError = crossbeam_channel::RecvError;
 #[cfg(feature = "runtime_thread")]
+pub type RecvTimeoutError = crossbeam_channel::RecvTimeoutError;
+#[cfg(feature = "runtime_thread")]
 pub type JoinHandle<T> = std::thread::JoinHandle<T>;
 #[cfg(feature = "runtime_thread")]
 pub type WaitGroup = crossbeam_utils::sync::WaitGroup;
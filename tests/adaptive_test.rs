@@ -0,0 +1,51 @@
+#[cfg(test)]
+mod test {
+    use fast_log::adaptive::AdaptiveLevel;
+    use fast_log::appender::{Command, FastLogRecord};
+    use log::{Level, LevelFilter};
+    use std::thread::sleep;
+    use std::time::{Duration, SystemTime};
+
+    #[test]
+    fn test_adaptive_level() {
+        fast_log::logger().set_level(LevelFilter::Info);
+        let adaptive = AdaptiveLevel::new(
+            3,
+            Duration::from_secs(1),
+            LevelFilter::Debug,
+            Duration::from_millis(200),
+        );
+        let burst = || {
+            let mut records = vec![];
+            for _ in 0..4 {
+                records.push(FastLogRecord {
+                    command: Command::CommandRecord,
+                    level: Level::Error,
+                    target: "".to_string(),
+                    args: "".to_string(),
+                    module_path: "".to_string(),
+                    file: "".to_string(),
+                    line: None,
+                    now: SystemTime::now(),
+                    formated: "".to_string(),
+                });
+            }
+            records
+        };
+        let mut records = burst();
+        adaptive.do_watch(&mut records);
+        assert_eq!(records.len(), 5);
+        assert!(adaptive.is_active());
+        assert_eq!(fast_log::logger().get_level(), LevelFilter::Debug);
+        sleep(Duration::from_millis(600));
+        assert!(!adaptive.is_active());
+        assert_eq!(fast_log::logger().get_level(), LevelFilter::Info);
+        //the level set manually while the burst is kept
+        adaptive.do_watch(&mut burst());
+        assert_eq!(fast_log::logger().get_level(), LevelFilter::Debug);
+        fast_log::logger().set_level(LevelFilter::Warn);
+        sleep(Duration::from_millis(600));
+        assert!(!adaptive.is_active());
+        assert_eq!(fast_log::logger().get_level(), LevelFilter::Warn);
+    }
+}
@@ -0,0 +1,55 @@
+#[cfg(test)]
+mod test {
+    use fast_log::appender::{Command, FastLogRecord};
+    use fast_log::plugin::file_name::ArchiveMatcher;
+    use fast_log::plugin::file_split::{CanRollingPack, DateType, Rolling, RollingType};
+    use fast_log::plugin::packer::LogPacker;
+    use fast_log::TimeType;
+    use log::Level;
+    use std::time::{Duration, UNIX_EPOCH};
+
+    //the daylight saving time ends at 2026-11-01 02:00 EDT
+    const TZ: &str = "EST5EDT,M3.2.0,M11.1.0";
+
+    fn record(sec: u64) -> FastLogRecord {
+        FastLogRecord {
+            command: Command::CommandRecord,
+            level: Level::Info,
+            target: "".to_string(),
+            args: "".to_string(),
+            module_path: "".to_string(),
+            file: "".to_string(),
+            line: None,
+            now: UNIX_EPOCH + Duration::from_secs(sec),
+            formated: "".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_date_rolling_dst() {
+        std::env::set_var("TZ", TZ);
+        let mut rolling = Rolling::new(RollingType::ByDate(DateType::Day));
+        //2026-11-01 22:00 EST
+        rolling.can(&LogPacker {}, "temp.log", 0, &record(1793588400));
+        //2026-11-01 23:30 EST
+        assert_eq!(rolling.can(&LogPacker {}, "temp.log", 0, &record(1793593800)), None);
+        //2026-11-02 00:30 EST
+        assert_eq!(
+            rolling.can(&LogPacker {}, "temp.log", 0, &record(1793597400)),
+            Some("temp2026-11-01T23-30-00.000000.log".to_string())
+        );
+    }
+
+    #[test]
+    fn test_archive_time_dst() {
+        std::env::set_var("TZ", TZ);
+        let matcher = ArchiveMatcher::new("temp.log");
+        let edt = matcher.parse("temp2026-10-31T23-30-00.000000.log").unwrap();
+        assert_eq!(edt.time, Some(UNIX_EPOCH + Duration::from_secs(1793503800)));
+        let est = matcher.parse("temp2026-11-02T00-30-00.000000.log").unwrap();
+        assert_eq!(est.time, Some(UNIX_EPOCH + Duration::from_secs(1793597400)));
+        let utc = ArchiveMatcher::new("temp.log").time_type(TimeType::Utc);
+        let utc = utc.parse("temp2026-11-02T05-30-00.000000.log").unwrap();
+        assert_eq!(utc.time, Some(UNIX_EPOCH + Duration::from_secs(1793597400)));
+    }
+}
@@ -0,0 +1,72 @@
+#[cfg(test)]
+mod test {
+    use fast_log::appender::{Command, FastLogRecord};
+    use fast_log::plugin::durability::{Durability, SyncState};
+    use fast_log::Config;
+    use log::{Level, LevelFilter};
+    use std::fs::remove_dir_all;
+    use std::thread::sleep;
+    use std::time::{Duration, SystemTime};
+
+    fn record(level: Level) -> FastLogRecord {
+        FastLogRecord {
+            command: Command::CommandRecord,
+            level,
+            target: "".to_string(),
+            args: "".to_string(),
+            module_path: "".to_string(),
+            file: "".to_string(),
+            line: None,
+            now: SystemTime::now(),
+            formated: "".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_sync_state() {
+        let mut state = SyncState::new(Durability::Never);
+        assert!(!state.need_sync(&[record(Level::Error)]));
+        let sync = FastLogRecord {
+            command: Command::CommandSync(fast_log::WaitGroup::new()),
+            ..record(Level::Info)
+        };
+        assert!(state.need_sync(&[sync]));
+
+        let mut state = SyncState::new(Durability::EveryRecords(2));
+        assert!(!state.need_sync(&[record(Level::Info)]));
+        assert!(state.need_sync(&[record(Level::Info)]));
+        state.synced();
+        assert!(!state.need_sync(&[record(Level::Info)]));
+
+        let mut state = SyncState::new(Durability::OnError);
+        assert!(!state.need_sync(&[record(Level::Warn)]));
+        assert!(state.need_sync(&[record(Level::Info), record(Level::Error)]));
+
+        let mut state = SyncState::new(Durability::Interval(Duration::from_millis(50)));
+        assert!(!state.need_sync(&[record(Level::Info)]));
+        assert!(!state.need_sync_tick());
+        sleep(Duration::from_millis(60));
+        assert!(state.need_sync_tick());
+        state.synced();
+        //nothing written
+        sleep(Duration::from_millis(60));
+        assert!(!state.need_sync_tick());
+    }
+
+    #[test]
+    fn test_sync_level() {
+        let _ = remove_dir_all("target/test_sync/");
+        fast_log::init(
+            Config::new()
+                .durability(Durability::OnError)
+                .sync_level(LevelFilter::Warn)
+                .file("target/test_sync/test.log"),
+        )
+        .unwrap();
+        log::warn!("warn is written before return");
+        let data = std::fs::read_to_string("target/test_sync/test.log").unwrap();
+        assert!(data.contains("warn is written before return"));
+        log::logger().flush();
+        let _ = remove_dir_all("target/test_sync/");
+    }
+}
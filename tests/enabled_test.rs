@@ -0,0 +1,22 @@
+#[cfg(test)]
+mod test {
+    use fast_log::filter::ModuleFilter;
+    use fast_log::Config;
+    use log::LevelFilter;
+
+    #[test]
+    fn test_enabled() {
+        let m = ModuleFilter::new();
+        m.modules.push(module_path!().to_string());
+        fast_log::init(Config::new()
+            .console()
+            .add_filter(m)
+            .target_level("my_crate::db", LevelFilter::Info)
+        ).unwrap();
+        assert!(!log::log_enabled!(target: "my_crate::db::pool", log::Level::Debug));
+        assert!(log::log_enabled!(target: "my_crate::db::pool", log::Level::Info));
+        assert!(log::log_enabled!(target: "my_crate::web", log::Level::Debug));
+        //the ModuleFilter filters by module path, an record of target same as the module may come from other module
+        assert!(log::log_enabled!(target: module_path!(), log::Level::Info));
+    }
+}
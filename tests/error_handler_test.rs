@@ -0,0 +1,60 @@
+#[cfg(test)]
+mod test {
+    use fast_log::consts::LogSize;
+    use fast_log::error::{ErrorHandler, LogError, ReportError};
+    use fast_log::plugin::file_split::{KeepType, Packer, Rolling, RollingType};
+    use fast_log::Config;
+    use std::fs::{remove_dir_all, File};
+    use std::sync::{Arc, Mutex};
+    use std::thread::sleep;
+    use std::time::Duration;
+
+    struct FailPacker {}
+    impl Packer for FailPacker {
+        fn pack_name(&self) -> &'static str {
+            "fail"
+        }
+
+        fn do_pack(&self, _log_file: File, _log_file_path: &str) -> Result<bool, LogError> {
+            Err(LogError::from("no space left"))
+        }
+    }
+
+    struct VecHandler {
+        errors: Arc<Mutex<Vec<ReportError>>>,
+    }
+    impl ErrorHandler for VecHandler {
+        fn handle(&self, error: &ReportError) {
+            self.errors.lock().unwrap().push(error.clone());
+        }
+    }
+
+    #[test]
+    fn test_error_handler() {
+        let _ = remove_dir_all("target/test_error_handler/");
+        let errors = Arc::new(Mutex::new(vec![]));
+        fast_log::init(
+            Config::new()
+                .error_handler(VecHandler {
+                    errors: errors.clone(),
+                })
+                .file_split(
+                    "target/test_error_handler/",
+                    Rolling::new(RollingType::BySize(LogSize::B(10))),
+                    KeepType::All,
+                    FailPacker {},
+                ),
+        )
+        .unwrap();
+        log::info!("Commencing yak shaving");
+        log::info!("Commencing yak shaving");
+        log::logger().flush();
+        sleep(Duration::from_millis(500));
+        let errors = errors.lock().unwrap();
+        assert!(!errors.is_empty());
+        assert_eq!(errors[0].component, "Packer");
+        assert_eq!(errors[0].action, "pack");
+        assert!(errors[0].message.contains("no space left"));
+        let _ = remove_dir_all("target/test_error_handler/");
+    }
+}
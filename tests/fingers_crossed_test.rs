@@ -0,0 +1,48 @@
+#[cfg(test)]
+mod test {
+    use fast_log::appender::{Command, FastLogRecord};
+    use fast_log::fingers_crossed::FingersCrossed;
+    use log::Level;
+    use std::time::SystemTime;
+
+    fn record(level: Level, target: &str, args: &str) -> FastLogRecord {
+        FastLogRecord {
+            command: Command::CommandRecord,
+            level,
+            target: target.to_string(),
+            args: args.to_string(),
+            module_path: target.to_string(),
+            file: "".to_string(),
+            line: None,
+            now: SystemTime::now(),
+            formated: format!("{}\n", args),
+        }
+    }
+
+    #[test]
+    fn test_fingers_crossed() {
+        let f = FingersCrossed::new(2);
+        let out = f.do_filter(vec![
+            record(Level::Debug, "a", "1"),
+            record(Level::Debug, "a", "2"),
+            record(Level::Info, "a", "3"),
+            record(Level::Debug, "a", "4"),
+        ]);
+        assert_eq!(out.len(), 1);
+        assert_eq!(f.buffered(), 2);
+        let out = f.do_filter(vec![record(Level::Error, "a", "5")]);
+        let args: Vec<&str> = out.iter().map(|x| x.args.as_str()).collect();
+        assert_eq!(args, vec!["2", "4", "5"]);
+        assert_eq!(f.buffered(), 0);
+    }
+
+    #[test]
+    fn test_fingers_crossed_per_target() {
+        let f = FingersCrossed::new(10).per_target(true);
+        f.do_filter(vec![record(Level::Debug, "a", "1"), record(Level::Debug, "b", "2")]);
+        let out = f.do_filter(vec![record(Level::Error, "b", "3")]);
+        let args: Vec<&str> = out.iter().map(|x| x.args.as_str()).collect();
+        assert_eq!(args, vec!["2", "3"]);
+        assert_eq!(f.buffered(), 1);
+    }
+}
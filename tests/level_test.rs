@@ -0,0 +1,48 @@
+#[cfg(test)]
+mod test {
+    use fast_log::level::LevelControl;
+    use log::LevelFilter;
+    use std::thread::sleep;
+    use std::time::Duration;
+
+    #[test]
+    fn test_target_level() {
+        let levels = LevelControl::new(LevelFilter::Info);
+        levels.set_target_level("my_crate::db", LevelFilter::Trace, None);
+        levels.set_target_level("my_crate", LevelFilter::Warn, None);
+        assert_eq!(levels.level_for("my_crate::db::pool"), LevelFilter::Trace);
+        assert_eq!(levels.level_for("my_crate::web"), LevelFilter::Warn);
+        assert_eq!(levels.level_for("other"), LevelFilter::Info);
+        assert_eq!(levels.target_levels().len(), 2);
+        assert!(levels.remove_target_level("my_crate::db"));
+        assert_eq!(levels.level_for("my_crate::db::pool"), LevelFilter::Warn);
+    }
+
+    #[test]
+    fn test_target_level_expire() {
+        let levels = LevelControl::new(LevelFilter::Info);
+        levels.set_target_level("my_crate::db", LevelFilter::Trace, Some(Duration::from_millis(100)));
+        assert_eq!(levels.level_for("my_crate::db"), LevelFilter::Trace);
+        sleep(Duration::from_millis(500));
+        assert_eq!(levels.level_for("my_crate::db"), LevelFilter::Info);
+        assert!(levels.target_levels().is_empty());
+    }
+
+    #[test]
+    fn test_target_level_boundary() {
+        let levels = LevelControl::new(LevelFilter::Info);
+        levels.set_target_level("my_crate", LevelFilter::Trace, None);
+        assert_eq!(levels.level_for("my_crate"), LevelFilter::Trace);
+        assert_eq!(levels.level_for("my_crate::db"), LevelFilter::Trace);
+        assert_eq!(levels.level_for("my_crate_other"), LevelFilter::Info);
+    }
+
+    #[test]
+    fn test_standalone_not_global() {
+        let max = log::max_level();
+        let levels = LevelControl::new(LevelFilter::Off);
+        levels.set_target_level("my_crate", LevelFilter::Trace, None);
+        levels.set_level(LevelFilter::Error);
+        assert_eq!(log::max_level(), max);
+    }
+}
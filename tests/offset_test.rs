@@ -0,0 +1,45 @@
+#[cfg(test)]
+mod test {
+    use fast_log::appender::{FastLogRecord, LogAppender};
+    use fast_log::plugin::file_split::{CanRollingPack, FileSplitAppender, KeepType, Packer, RawFile};
+    use fast_log::plugin::packer::LogPacker;
+    use parking_lot::Mutex;
+    use std::fs::remove_dir_all;
+    use std::sync::Arc;
+    use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+    const OFFSET: i32 = 8 * 3600;
+
+    struct Capture(Arc<Mutex<Option<SystemTime>>>);
+
+    impl CanRollingPack for Capture {
+        fn can(&mut self, _: &dyn Packer, _: &str, _: usize, _: &FastLogRecord) -> Option<String> {
+            None
+        }
+
+        fn can_recover(&mut self, _: &dyn Packer, _: &str, _: usize, file_time: SystemTime, _: &FastLogRecord) -> Option<String> {
+            *self.0.lock() = Some(file_time);
+            None
+        }
+    }
+
+    #[test]
+    fn test_first_record_time() {
+        fastdate::set_offset_sec(OFFSET);
+        let _ = remove_dir_all("target/test_offset/");
+        std::fs::create_dir_all("target/test_offset/").unwrap();
+        //1970-01-01 23:00:00 utc
+        std::fs::write("target/test_offset/temp.log", "1970-01-02 07:00:00.000000 [INFO] a\n").unwrap();
+        let time = Arc::new(Mutex::new(None));
+        let mut appender = FileSplitAppender::new::<RawFile>(
+            "target/test_offset/",
+            Box::new(Capture(time.clone())),
+            Box::new(KeepType::All),
+            Box::new(LogPacker {}),
+        )
+        .unwrap();
+        //recovered on the first tick
+        appender.do_tick(SystemTime::now());
+        assert_eq!(*time.lock(), Some(UNIX_EPOCH + Duration::from_secs(23 * 3600)));
+    }
+}
@@ -0,0 +1,108 @@
+#[cfg(test)]
+mod test {
+    use fast_log::appender::{Command, FastLogRecord, LogAppender};
+    use fast_log::consts::LogSize;
+    use fast_log::plugin::file::FileAppender;
+    use fast_log::plugin::file_split::{FileSplitAppender, KeepType, RawFile, Rolling, RollingType};
+    use fast_log::plugin::packer::LogPacker;
+    use log::Level;
+    use std::fs::{remove_dir_all, OpenOptions};
+    use std::time::{Duration, SystemTime};
+
+    fn record(formated: &str) -> FastLogRecord {
+        record_at(formated, SystemTime::now())
+    }
+
+    fn record_at(formated: &str, now: SystemTime) -> FastLogRecord {
+        FastLogRecord {
+            command: Command::CommandRecord,
+            level: Level::Info,
+            target: "".to_string(),
+            args: "".to_string(),
+            module_path: "".to_string(),
+            file: "".to_string(),
+            line: None,
+            now,
+            formated: formated.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_reopen_moved_file() {
+        let _ = remove_dir_all("target/test_reopen_file/");
+        let mut appender = FileAppender::new("target/test_reopen_file/test.log").unwrap();
+        appender.do_logs(&[record("first\n")]);
+        std::fs::rename("target/test_reopen_file/test.log", "target/test_reopen_file/test.log.1").unwrap();
+        appender.do_tick(SystemTime::now());
+        appender.do_logs(&[record("second\n")]);
+        assert_eq!(std::fs::read_to_string("target/test_reopen_file/test.log.1").unwrap(), "first\n");
+        assert_eq!(std::fs::read_to_string("target/test_reopen_file/test.log").unwrap(), "second\n");
+        let _ = remove_dir_all("target/test_reopen_file/");
+    }
+
+    #[test]
+    fn test_reopen_split_file() {
+        let dir = "target/test_reopen_split/";
+        let _ = remove_dir_all(dir);
+        let mut appender = FileSplitAppender::new::<RawFile>(
+            dir,
+            Box::new(Rolling::new(RollingType::BySize(LogSize::MB(1)))),
+            Box::new(KeepType::All),
+            Box::new(LogPacker {}),
+        )
+            .unwrap();
+        //moved
+        appender.do_logs(&[record("first\n")]);
+        std::fs::rename(format!("{}temp.log", dir), format!("{}moved.log", dir)).unwrap();
+        appender.do_tick(SystemTime::now());
+        appender.do_logs(&[record("second\n")]);
+        assert_eq!(std::fs::read_to_string(format!("{}moved.log", dir)).unwrap(), "first\n");
+        assert_eq!(std::fs::read_to_string(format!("{}temp.log", dir)).unwrap(), "second\n");
+        //truncated(copytruncate)
+        OpenOptions::new().write(true).open(format!("{}temp.log", dir)).unwrap().set_len(0).unwrap();
+        appender.do_tick(SystemTime::now());
+        appender.do_logs(&[record("third\n")]);
+        assert_eq!(std::fs::read_to_string(format!("{}temp.log", dir)).unwrap(), "third\n");
+        //reopen by api
+        std::fs::remove_file(format!("{}temp.log", dir)).unwrap();
+        appender.reopen();
+        appender.do_logs(&[record("fourth\n")]);
+        assert_eq!(std::fs::read_to_string(format!("{}temp.log", dir)).unwrap(), "fourth\n");
+        let _ = remove_dir_all(dir);
+    }
+
+    #[test]
+    fn test_reopen_busy() {
+        let dir = "target/test_reopen_busy/";
+        let _ = remove_dir_all(dir);
+        let mut file = FileAppender::new(&format!("{}file.log", dir)).unwrap();
+        let mut split = FileSplitAppender::new::<RawFile>(
+            dir,
+            Box::new(Rolling::new(RollingType::BySize(LogSize::MB(1)))),
+            Box::new(KeepType::All),
+            Box::new(LogPacker {}),
+        )
+            .unwrap();
+        let start = SystemTime::now();
+        file.do_logs(&[record_at("first\n", start)]);
+        split.do_logs(&[record_at("first\n", start)]);
+        std::fs::rename(format!("{}file.log", dir), format!("{}file.log.1", dir)).unwrap();
+        std::fs::rename(format!("{}temp.log", dir), format!("{}moved.log", dir)).unwrap();
+        //the records keep arriving, no tick
+        for i in 1..=5 {
+            let now = start + Duration::from_millis(300 * i);
+            file.do_logs(&[record_at(&format!("{}\n", i), now)]);
+            split.do_logs(&[record_at(&format!("{}\n", i), now)]);
+        }
+        //checked once a second, the later records are written into the new file
+        let all = "first\n1\n2\n3\n4\n5\n";
+        for (old, new) in [("file.log.1", "file.log"), ("moved.log", "temp.log")] {
+            let old = std::fs::read_to_string(format!("{}{}", dir, old)).unwrap();
+            let new = std::fs::read_to_string(format!("{}{}", dir, new)).unwrap();
+            assert!(old.starts_with("first\n1\n"));
+            assert!(new.ends_with("4\n5\n"));
+            assert_eq!(format!("{}{}", old, new), all);
+        }
+        let _ = remove_dir_all(dir);
+    }
+}
@@ -0,0 +1,39 @@
+#[cfg(test)]
+mod test {
+    use fast_log::plugin::schedule::Schedule;
+    use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+    fn time(sec: u64) -> SystemTime {
+        UNIX_EPOCH + Duration::from_secs(sec)
+    }
+
+    #[test]
+    fn test_every_aligned() {
+        let s = Schedule::every(Duration::from_secs(15 * 60)).utc();
+        //1970-01-01 00:07:00 => 00:15:00
+        assert_eq!(s.next_after(time(7 * 60)), time(15 * 60));
+        assert_eq!(s.next_after(time(15 * 60)), time(30 * 60));
+    }
+
+    #[test]
+    fn test_cron() {
+        let s = Schedule::cron("0 0 * * *").unwrap().utc();
+        assert_eq!(s.next_after(time(100)), time(86400));
+        //every monday 08:30, 1970-01-01 is thursday
+        let s = Schedule::cron("30 8 * * 1").unwrap().utc();
+        assert_eq!(s.next_after(time(0)), time(4 * 86400 + 8 * 3600 + 30 * 60));
+        let s = Schedule::cron("*/20 * * * *").unwrap().utc();
+        assert_eq!(s.next_after(time(21 * 60)), time(40 * 60));
+        assert!(Schedule::cron("0 0 * *").is_err());
+        assert!(Schedule::cron("61 0 * * *").is_err());
+    }
+
+    #[test]
+    fn test_is_due() {
+        let mut s = Schedule::every(Duration::from_secs(60)).utc();
+        assert!(!s.is_due(time(10), time(50)));
+        assert!(s.is_due(time(10), time(61)));
+        assert!(!s.is_due(time(61), time(100)));
+        assert!(s.is_due(time(100), time(125)));
+    }
+}
@@ -2,8 +2,8 @@
 mod test {
     use fast_log::appender::{Command, FastLogRecord, LogAppender};
     use fast_log::consts::LogSize;
-    use fast_log::plugin::file_name::FileName;
-    use fast_log::plugin::file_split::{FileSplitAppender, RollingType, Keep, RawFile, Rolling, KeepType};
+    use fast_log::plugin::file_name::{ArchiveMatcher, FileName, NameTemplate};
+    use fast_log::plugin::file_split::{archive_index, ArchiveLayout, CanRollingPack, CompositeRolling, DateType, FileSplitAppender, RollingType, Keep, RawFile, Rolling, KeepType, KeepPolicy, NameType, RotateType};
     use fast_log::plugin::packer::LogPacker;
     use fastdate::DateTime;
     use log::Level;
@@ -40,6 +40,863 @@ mod test {
     }
 
 
+    #[test]
+    fn test_index_name() {
+        let _ = remove_dir_all("target/test_index/");
+        let mut appender = FileSplitAppender::new::<RawFile>(
+            "target/test_index/temp.log",
+            Box::new(Rolling::new(RollingType::BySize(LogSize::MB(1))).name_type(NameType::Index)),
+            Box::new(KeepType::All),
+            Box::new(LogPacker {}),
+        )
+            .unwrap();
+        for i in 0..3 {
+            appender.send_pack(format!("temp{}.log", i), None);
+        }
+        sleep(Duration::from_secs(1));
+        for i in 1..4 {
+            assert!(std::path::Path::new(&format!("target/test_index/temp.log.{}", i)).exists());
+        }
+        let removed = KeepType::KeepNum(2).do_keep("target/test_index/", "temp.log");
+        assert_eq!(removed, 1);
+        assert!(!std::path::Path::new("target/test_index/temp.log.3").exists());
+        let _ = remove_dir_all("target/test_index/");
+    }
+
+    #[test]
+    fn test_rotate() {
+        for (dir, rotate_type) in [("target/test_rename/", RotateType::Rename), ("target/test_copy/", RotateType::Copy)] {
+            let _ = remove_dir_all(dir);
+            let mut appender = FileSplitAppender::new::<RawFile>(
+                dir,
+                Box::new(Rolling::new(RollingType::BySize(LogSize::MB(1)))),
+                Box::new(KeepType::All),
+                Box::new(LogPacker {}),
+            )
+                .unwrap()
+                .rotate_type(rotate_type);
+            let mut r = record(SystemTime::now());
+            r.formated = "first\n".to_string();
+            appender.do_logs(&[r.clone()]);
+            appender.send_pack("temp-old.log".to_string(), None);
+            r.formated = "second\n".to_string();
+            appender.do_logs(&[r]);
+            appender.do_logs(&[FastLogRecord { command: Command::CommandExit, ..record(SystemTime::now()) }]);
+            assert_eq!(std::fs::read_to_string(format!("{}temp-old.log", dir)).unwrap(), "first\n");
+            assert_eq!(std::fs::read_to_string(format!("{}temp.log", dir)).unwrap(), "second\n");
+            let _ = remove_dir_all(dir);
+        }
+    }
+
+    #[test]
+    fn test_rotate_open_fail() {
+        use fast_log::error::LogError;
+        use fast_log::plugin::file_split::SplitFile;
+        use std::io::SeekFrom;
+        use std::sync::atomic::{AtomicBool, Ordering};
+        static FAIL: AtomicBool = AtomicBool::new(false);
+        struct FailOpenFile(RawFile);
+        impl SplitFile for FailOpenFile {
+            fn new(path: &str) -> Result<Self, LogError> {
+                if FAIL.load(Ordering::SeqCst) {
+                    return Err(LogError::from("open fail"));
+                }
+                Ok(Self(RawFile::new(path)?))
+            }
+            fn seek(&self, pos: SeekFrom) -> std::io::Result<u64> { self.0.seek(pos) }
+            fn write(&self, buf: &[u8]) -> std::io::Result<usize> { self.0.write(buf) }
+            fn truncate(&self) -> std::io::Result<()> { self.0.truncate() }
+            fn flush(&self) { self.0.flush() }
+            fn len(&self) -> usize { self.0.len() }
+            fn offset(&self) -> usize { self.0.offset() }
+        }
+        let dir = "target/test_rotate_open_fail/";
+        let _ = remove_dir_all(dir);
+        let mut appender = FileSplitAppender::new::<FailOpenFile>(
+            dir,
+            Box::new(Rolling::new(RollingType::BySize(LogSize::MB(1)))),
+            Box::new(KeepType::All),
+            Box::new(LogPacker {}),
+        )
+            .unwrap();
+        let mut r = record(SystemTime::now());
+        r.formated = "first\n".to_string();
+        appender.do_logs(&[r.clone()]);
+        //the rename is restored, fall back to copy
+        FAIL.store(true, Ordering::SeqCst);
+        appender.send_pack("temp-old.log".to_string(), None);
+        FAIL.store(false, Ordering::SeqCst);
+        r.formated = "second\n".to_string();
+        appender.do_logs(&[r]);
+        assert_eq!(std::fs::read_to_string(format!("{}temp-old.log", dir)).unwrap(), "first\n");
+        assert_eq!(std::fs::read_to_string(format!("{}temp.log", dir)).unwrap(), "second\n");
+        let _ = remove_dir_all(dir);
+    }
+
+    #[test]
+    fn test_tick_rolling() {
+        let _ = remove_dir_all("target/test_tick/");
+        let mut appender = FileSplitAppender::new::<RawFile>(
+            "target/test_tick/",
+            Box::new(Rolling::new(RollingType::ByDate(DateType::Sec))),
+            Box::new(KeepType::All),
+            Box::new(LogPacker {}),
+        )
+            .unwrap();
+        let now = SystemTime::now();
+        let mut r = record(now);
+        r.formated = "first\n".to_string();
+        appender.do_logs(&[r]);
+        appender.do_tick(now + Duration::from_secs(2));
+        //the file is empty, not roll again
+        appender.do_tick(now + Duration::from_secs(4));
+        assert_eq!(std::fs::read_dir("target/test_tick/").unwrap().count(), 2);
+        let _ = remove_dir_all("target/test_tick/");
+    }
+
+    #[test]
+    fn test_force_roll() {
+        let _ = remove_dir_all("target/test_roll/");
+        let mut appender = FileSplitAppender::new::<RawFile>(
+            "target/test_roll/",
+            Box::new(Rolling::new(RollingType::BySize(LogSize::MB(1)))),
+            Box::new(KeepType::All),
+            Box::new(LogPacker {}),
+        )
+            .unwrap();
+        let mut r = record(SystemTime::now());
+        r.formated = "first\n".to_string();
+        let wg = fast_log::WaitGroup::new();
+        appender.do_logs(&[r, FastLogRecord { command: Command::CommandRoll(wg.clone()), ..record(SystemTime::now()) }]);
+        //the file is empty, not roll again
+        appender.do_logs(&[FastLogRecord { command: Command::CommandRoll(wg.clone()), ..record(SystemTime::now()) }]);
+        wg.wait();
+        let archives = ArchiveMatcher::new("temp.log").read_dir("target/test_roll/");
+        assert_eq!(archives.len(), 1);
+        assert_eq!(std::fs::read_to_string(&archives[0].path).unwrap(), "first\n");
+        assert_eq!(std::fs::read_to_string("target/test_roll/temp.log").unwrap(), "");
+        let _ = remove_dir_all("target/test_roll/");
+    }
+
+    #[test]
+    fn test_recover() {
+        use fast_log::error::LogError;
+        use fast_log::plugin::file_split::{pack_path, Packer};
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        static PACKS: AtomicUsize = AtomicUsize::new(0);
+        struct CopyPacker {}
+        impl Packer for CopyPacker {
+            fn pack_name(&self) -> &'static str {
+                "pack"
+            }
+
+            fn do_pack(&self, _log_file: std::fs::File, log_file_path: &str) -> Result<bool, LogError> {
+                std::fs::copy(log_file_path, pack_path(log_file_path, self.pack_name()))?;
+                PACKS.fetch_add(1, Ordering::SeqCst);
+                Ok(true)
+            }
+        }
+        let dir = "target/test_recover/";
+        let _ = remove_dir_all(dir);
+        std::fs::create_dir_all(dir).unwrap();
+        let yesterday = DateTime::now().sub(Duration::from_secs(24 * 3600));
+        std::fs::write(
+            format!("{}temp.log", dir),
+            format!("{} [INFO] old\n", yesterday.format("YYYY-MM-DD hh:mm:ss.000000")),
+        )
+            .unwrap();
+        let archive_dir = "target/test_recover/archive/";
+        std::fs::create_dir_all(archive_dir).unwrap();
+        std::fs::write(format!("{}temp2020-01-01T00-00-00.000000.log", archive_dir), "orphan\n").unwrap();
+        let mut appender = FileSplitAppender::new::<RawFile>(
+            dir,
+            Box::new(Rolling::new(RollingType::ByDate(DateType::Day))),
+            Box::new(KeepType::All),
+            Box::new(CopyPacker {}),
+        )
+            .unwrap()
+            .archive_dir(archive_dir)
+            .archive_layout(ArchiveLayout::Flat);
+        //the active file is rolled once the archive_dir is set, the orphans are scanned once
+        appender.do_tick(SystemTime::now());
+        appender.do_tick(SystemTime::now());
+        sleep(Duration::from_secs(1));
+        assert_eq!(PACKS.load(Ordering::SeqCst), 2);
+        assert_eq!(std::fs::read_to_string(format!("{}temp.log", dir)).unwrap(), "");
+        let mut names: Vec<String> = std::fs::read_dir(archive_dir)
+            .unwrap()
+            .map(|x| x.unwrap().file_name().to_string_lossy().to_string())
+            //the manifest of feature "manifest"
+            .filter(|x| x != "manifest.jsonl")
+            .collect();
+        names.sort();
+        assert_eq!(names.len(), 2);
+        assert_eq!(names[0], "temp2020-01-01T00-00-00.000000.pack");
+        assert!(names[1].starts_with(&yesterday.format("tempYYYY-MM-DD")));
+        assert!(names[1].ends_with(".pack"));
+        let _ = remove_dir_all(dir);
+    }
+
+    #[test]
+    fn test_keep_size() {
+        let dir = "target/test_keep_size/";
+        let _ = remove_dir_all(dir);
+        std::fs::create_dir_all(dir).unwrap();
+        std::fs::write(format!("{}temp.log", dir), "active").unwrap();
+        for i in 1..5 {
+            std::fs::write(format!("{}temp2020-01-0{}T00-00-00.000000.log", dir, i), "0123456789").unwrap();
+        }
+        //the newest 2 packs are kept
+        assert_eq!(KeepType::KeepSize(LogSize::B(25)).do_keep(dir, "temp.log"), 2);
+        assert!(std::path::Path::new(&format!("{}temp2020-01-04T00-00-00.000000.log", dir)).exists());
+        assert!(std::path::Path::new(&format!("{}temp2020-01-03T00-00-00.000000.log", dir)).exists());
+        assert!(std::path::Path::new(&format!("{}temp.log", dir)).exists());
+        let keep = KeepPolicy::new().keep_size(LogSize::KB(1)).keep_num(1);
+        assert_eq!(keep.do_keep(dir, "temp.log"), 1);
+        let _ = remove_dir_all(dir);
+    }
+
+    #[test]
+    fn test_archive_dir() {
+        let _ = remove_dir_all("target/test_archive_dir/");
+        let mut appender = FileSplitAppender::new::<RawFile>(
+            "target/test_archive_dir/temp.log",
+            Box::new(Rolling::new(RollingType::BySize(LogSize::MB(1)))),
+            Box::new(KeepType::KeepNum(1)),
+            Box::new(LogPacker {}),
+        )
+            .unwrap()
+            .archive_dir("target/test_archive_dir/archive")
+            .archive_layout(ArchiveLayout::Date);
+        appender.do_logs(&[record(SystemTime::now())]);
+        appender.send_pack("temp2020-01-02T00-00-00.000000.log".to_string(), None);
+        sleep(Duration::from_secs(1));
+        assert!(std::path::Path::new("target/test_archive_dir/archive/2020/01/02/temp2020-01-02T00-00-00.000000.log").exists());
+        appender.do_logs(&[record(SystemTime::now())]);
+        appender.send_pack("temp2020-01-03T00-00-00.000000.log".to_string(), None);
+        sleep(Duration::from_secs(1));
+        //the old archive and its empty directory are removed
+        assert!(std::path::Path::new("target/test_archive_dir/archive/2020/01/03/temp2020-01-03T00-00-00.000000.log").exists());
+        assert!(!std::path::Path::new("target/test_archive_dir/archive/2020/01/02").exists());
+        let _ = remove_dir_all("target/test_archive_dir/");
+    }
+
+    #[test]
+    fn test_archive_dir_orphans() {
+        use fast_log::error::LogError;
+        use fast_log::plugin::file_split::{pack_path, Packer};
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        static PACKS: AtomicUsize = AtomicUsize::new(0);
+        struct CopyPacker {}
+        impl Packer for CopyPacker {
+            fn pack_name(&self) -> &'static str {
+                "pack"
+            }
+
+            fn do_pack(&self, _log_file: std::fs::File, log_file_path: &str) -> Result<bool, LogError> {
+                std::fs::copy(log_file_path, pack_path(log_file_path, self.pack_name()))?;
+                PACKS.fetch_add(1, Ordering::SeqCst);
+                Ok(true)
+            }
+        }
+        let dir = "target/test_archive_dir_orphans/";
+        let _ = remove_dir_all(dir);
+        std::fs::create_dir_all(format!("{}archive/2020/01/02", dir)).unwrap();
+        std::fs::write(format!("{}temp2020-01-01T00-00-00.000000.log", dir), "a\n").unwrap();
+        std::fs::write(format!("{}archive/2020/01/02/temp2020-01-02T00-00-00.000000.log", dir), "b\n").unwrap();
+        let mut appender = FileSplitAppender::new::<RawFile>(
+            dir,
+            Box::new(Rolling::new(RollingType::BySize(LogSize::MB(1)))),
+            Box::new(KeepType::All),
+            Box::new(CopyPacker {}),
+        )
+            .unwrap()
+            .archive_dir(&format!("{}archive", dir))
+            .archive_layout(ArchiveLayout::Date);
+        appender.do_tick(SystemTime::now());
+        sleep(Duration::from_secs(1));
+        //the archive_dir inside the active directory is not scanned twice
+        assert_eq!(PACKS.load(Ordering::SeqCst), 2);
+        //the orphan of the active directory is moved to archive_dir
+        assert!(!std::path::Path::new(&format!("{}temp2020-01-01T00-00-00.000000.log", dir)).exists());
+        assert!(std::path::Path::new(&format!("{}archive/2020/01/01/temp2020-01-01T00-00-00.000000.pack", dir)).exists());
+        assert!(std::path::Path::new(&format!("{}archive/2020/01/02/temp2020-01-02T00-00-00.000000.pack", dir)).exists());
+        let _ = remove_dir_all(dir);
+    }
+
+    #[test]
+    fn test_pack_pool() {
+        use fast_log::error::LogError;
+        use fast_log::plugin::file_split::Packer;
+        use fast_log::plugin::pack_pool::{FullPolicy, PackPool};
+        struct SlowPacker {}
+        impl Packer for SlowPacker {
+            fn pack_name(&self) -> &'static str {
+                "slow"
+            }
+
+            fn do_pack(&self, _log_file: std::fs::File, _log_file_path: &str) -> Result<bool, LogError> {
+                sleep(Duration::from_millis(500));
+                Ok(false)
+            }
+        }
+        let dir = "target/test_pack_pool/";
+        let _ = remove_dir_all(dir);
+        let pool = PackPool::new(1, 1).full_policy(FullPolicy::Discard);
+        let mut appender = FileSplitAppender::new::<RawFile>(
+            dir,
+            Box::new(Rolling::new(RollingType::BySize(LogSize::MB(1)))),
+            Box::new(KeepType::All),
+            Box::new(SlowPacker {}),
+        )
+            .unwrap()
+            .pack_pool(pool.clone());
+        appender.send_pack("temp2020-01-01T00-00-00.000000.log".to_string(), None);
+        appender.send_pack("temp2020-01-02T00-00-00.000000.log".to_string(), None);
+        assert!(std::path::Path::new(&format!("{}temp2020-01-01T00-00-00.000000.log", dir)).exists());
+        //the pool is full, the second is discarded
+        assert!(!std::path::Path::new(&format!("{}temp2020-01-02T00-00-00.000000.log", dir)).exists());
+        sleep(Duration::from_secs(1));
+        assert_eq!(pool.pending(), 0);
+        let _ = remove_dir_all(dir);
+    }
+
+    #[test]
+    fn test_pack_pool_keep() {
+        use fast_log::error::LogError;
+        use fast_log::plugin::file_split::{pack_path, Packer};
+        use fast_log::plugin::pack_pool::PackPool;
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        static PACKS: AtomicUsize = AtomicUsize::new(0);
+        static FAILS: AtomicUsize = AtomicUsize::new(0);
+        struct SlowPacker {}
+        impl Packer for SlowPacker {
+            fn pack_name(&self) -> &'static str {
+                "slow"
+            }
+
+            fn do_pack(&self, _log_file: std::fs::File, log_file_path: &str) -> Result<bool, LogError> {
+                //the first pack is slower than the second
+                if PACKS.fetch_add(1, Ordering::SeqCst) == 0 {
+                    sleep(Duration::from_millis(500));
+                }
+                if let Err(e) = std::fs::copy(log_file_path, pack_path(log_file_path, self.pack_name())) {
+                    FAILS.fetch_add(1, Ordering::SeqCst);
+                    return Err(LogError::from(e));
+                }
+                Ok(true)
+            }
+        }
+        let dir = "target/test_pack_pool_keep/";
+        let _ = remove_dir_all(dir);
+        let pool = PackPool::new(2, 8);
+        let mut appender = FileSplitAppender::new::<RawFile>(
+            dir,
+            Box::new(Rolling::new(RollingType::BySize(LogSize::MB(1)))),
+            Box::new(KeepType::KeepNum(1)),
+            Box::new(SlowPacker {}),
+        )
+            .unwrap()
+            .pack_pool(pool.clone());
+        appender.send_pack("temp2020-01-01T00-00-00.000000.log".to_string(), None);
+        appender.send_pack("temp2020-01-02T00-00-00.000000.log".to_string(), None);
+        sleep(Duration::from_millis(1500));
+        assert_eq!(pool.pending(), 0);
+        //the keep of the second pack waits the first pack
+        assert_eq!(FAILS.load(Ordering::SeqCst), 0);
+        assert!(std::path::Path::new(&format!("{}temp2020-01-02T00-00-00.000000.slow", dir)).exists());
+        let _ = remove_dir_all(dir);
+    }
+
+    #[cfg(feature = "zstd")]
+    #[test]
+    fn test_zstd_packer() {
+        use fast_log::plugin::file_split::Packer;
+        use fast_log::plugin::packer::ZstdPacker;
+        let dir = "target/test_zstd_packer/";
+        let _ = remove_dir_all(dir);
+        std::fs::create_dir_all(dir).unwrap();
+        let log_path = format!("{}temp2020-01-01T00-00-00.000000.log", dir);
+        std::fs::write(&log_path, "Commencing yak shaving\n".repeat(100)).unwrap();
+        let packer = ZstdPacker::new().level(19).long_distance_matching(true);
+        let remove = packer.do_pack(std::fs::File::open(&log_path).unwrap(), &log_path).unwrap();
+        assert!(remove);
+        let data = std::fs::read(format!("{}temp2020-01-01T00-00-00.000000.zst", dir)).unwrap();
+        let data = zstd::decode_all(data.as_slice()).unwrap();
+        assert_eq!(data, "Commencing yak shaving\n".repeat(100).as_bytes());
+        let _ = remove_dir_all(dir);
+    }
+
+    #[cfg(feature = "zip")]
+    #[test]
+    fn test_zip_bundle() {
+        use fast_log::plugin::file_split::Packer;
+        use fast_log::plugin::packer::ZipPacker;
+        let dir = "target/test_zip_bundle/";
+        let _ = remove_dir_all(dir);
+        std::fs::create_dir_all(dir).unwrap();
+        let packer = ZipPacker::new().level(9).bundle_daily();
+        for hour in ["01", "02"] {
+            let log_path = format!("{}temp2020-01-01T{}-00-00.000000.log", dir, hour);
+            std::fs::write(&log_path, "Commencing yak shaving\n").unwrap();
+            assert!(packer.do_pack(std::fs::File::open(&log_path).unwrap(), &log_path).unwrap());
+        }
+        //an retry of the packed log file is skipped
+        let log_path = format!("{}temp2020-01-01T02-00-00.000000.log", dir);
+        std::fs::write(&log_path, "Commencing yak shaving\n").unwrap();
+        assert!(packer.do_pack(std::fs::File::open(&log_path).unwrap(), &log_path).unwrap());
+        let bundle = std::fs::File::open(format!("{}temp2020-01-01.zip", dir)).unwrap();
+        let zip = zip::ZipArchive::new(bundle).unwrap();
+        assert_eq!(zip.len(), 2);
+        assert!(!std::path::Path::new(&format!("{}temp2020-01-01.zip.tmp", dir)).exists());
+        let matcher = ArchiveMatcher::new("temp.log").pack_name("zip").bundle_format("YYYY-MM-DD");
+        assert!(matcher.parse("temp2020-01-01.zip").unwrap().time.is_some());
+        let _ = remove_dir_all(dir);
+    }
+
+    #[cfg(feature = "gzip")]
+    #[test]
+    fn test_compress_file() {
+        use fast_log::plugin::compress_file::GZipFile;
+        use std::io::Read;
+        let dir = "target/test_compress_file/";
+        let _ = remove_dir_all(dir);
+        let mut appender = FileSplitAppender::new::<GZipFile>(
+            "target/test_compress_file/temp.log.gz",
+            Box::new(Rolling::new(RollingType::BySize(LogSize::MB(1)))),
+            Box::new(KeepType::All),
+            Box::new(LogPacker {}),
+        )
+            .unwrap();
+        let decode = |path: &str| {
+            let mut data = String::new();
+            flate2::read::MultiGzDecoder::new(std::fs::File::open(path).unwrap())
+                .read_to_string(&mut data)
+                .unwrap();
+            data
+        };
+        let mut r = record(SystemTime::now());
+        r.formated = "1\n".to_string();
+        appender.do_logs(&[r.clone()]);
+        //the frame is written by tick
+        appender.do_tick(SystemTime::now());
+        r.formated = "2\n".to_string();
+        appender.do_logs(&[r]);
+        appender.do_tick(SystemTime::now());
+        assert_eq!(decode("target/test_compress_file/temp.log.gz"), "1\n2\n");
+        appender.send_pack("temp2020-01-01T00-00-00.000000.log.gz".to_string(), None);
+        assert_eq!(decode("target/test_compress_file/temp2020-01-01T00-00-00.000000.log.gz"), "1\n2\n");
+        let _ = remove_dir_all(dir);
+    }
+
+    #[cfg(feature = "gzip")]
+    #[test]
+    fn test_recover_compress_file() {
+        use fast_log::plugin::compress_file::GZipFile;
+        use std::io::Write;
+        let dir = "target/test_recover_compress_file/";
+        let _ = remove_dir_all(dir);
+        std::fs::create_dir_all(dir).unwrap();
+        let mut gz = flate2::write::GzEncoder::new(vec![], flate2::Compression::default());
+        gz.write_all(b"old\n").unwrap();
+        let path = format!("{}temp.log.gz", dir);
+        std::fs::write(&path, gz.finish().unwrap()).unwrap();
+        let yesterday = SystemTime::now() - Duration::from_secs(24 * 3600);
+        std::fs::File::options().write(true).open(&path).unwrap().set_modified(yesterday).unwrap();
+        let mut appender = FileSplitAppender::new::<GZipFile>(
+            &path,
+            Box::new(Rolling::new(RollingType::ByDate(DateType::Day))),
+            Box::new(KeepType::All),
+            Box::new(LogPacker {}),
+        )
+            .unwrap();
+        //the plain length of an exist compressed file is unknown, it is rolled by the file length
+        appender.do_tick(SystemTime::now());
+        sleep(Duration::from_millis(200));
+        let date = DateTime::from_system_time(yesterday, fastdate::offset_sec()).format("YYYY-MM-DD");
+        let rolled = std::fs::read_dir(dir)
+            .unwrap()
+            .flatten()
+            .any(|x| x.file_name().to_string_lossy().starts_with(&format!("temp.log{}", date)));
+        assert!(rolled);
+        let _ = remove_dir_all(dir);
+    }
+
+    #[cfg(all(feature = "encrypt", feature = "gzip"))]
+    #[test]
+    fn test_encrypt_packer() {
+        use fast_log::plugin::file_split::Packer;
+        use fast_log::plugin::packer::{decrypt, decrypt_file, EncryptPacker, GZipPacker};
+        use std::io::Read;
+        let dir = "target/test_encrypt_packer/";
+        let _ = remove_dir_all(dir);
+        std::fs::create_dir_all(dir).unwrap();
+        let log_path = format!("{}temp2020-01-01T00-00-00.000000.log", dir);
+        let text = "Commencing yak shaving\n".repeat(100);
+        std::fs::write(&log_path, &text).unwrap();
+        let key = [7u8; 32];
+        let packer = EncryptPacker::new(key).chunk_size(100).after(GZipPacker::new());
+        assert_eq!(packer.pack_name(), "gz.enc");
+        assert!(packer.do_pack(std::fs::File::open(&log_path).unwrap(), &log_path).unwrap());
+        let enc = std::fs::read(format!("{}temp2020-01-01T00-00-00.000000.gz.enc", dir)).unwrap();
+        assert!(!std::path::Path::new(&format!("{}temp2020-01-01T00-00-00.000000.gz", dir)).exists());
+        let mut gz = vec![];
+        decrypt(&key, enc.as_slice(), &mut gz).unwrap();
+        let mut data = String::new();
+        flate2::read::GzDecoder::new(gz.as_slice()).read_to_string(&mut data).unwrap();
+        assert_eq!(data, text);
+        //wrong key and truncated archive
+        assert!(decrypt(&[8u8; 32], enc.as_slice(), &mut vec![]).is_err());
+        assert!(decrypt(&key, &enc[..enc.len() - 30], &mut vec![]).is_err());
+        //an huge chunk size of the header is refused
+        let mut huge = enc.clone();
+        huge[4..8].copy_from_slice(&u32::MAX.to_le_bytes());
+        assert!(decrypt(&key, huge.as_slice(), &mut vec![]).is_err());
+        //no plain file is left if the archive is modified
+        let enc_path = format!("{}modified.gz.enc", dir);
+        let out_path = format!("{}modified.gz", dir);
+        let mut modified = enc.clone();
+        let idx = modified.len() - 1;
+        modified[idx] ^= 1;
+        std::fs::write(&enc_path, modified).unwrap();
+        assert!(decrypt_file(&key, &enc_path, &out_path).is_err());
+        assert!(!std::path::Path::new(&out_path).exists());
+        assert!(!std::path::Path::new(&format!("{}.tmp", out_path)).exists());
+        std::fs::write(&enc_path, &enc).unwrap();
+        decrypt_file(&key, &enc_path, &out_path).unwrap();
+        assert_eq!(std::fs::read(&out_path).unwrap(), gz);
+        let _ = remove_dir_all(dir);
+    }
+
+    #[cfg(all(feature = "encrypt", feature = "zip"))]
+    #[test]
+    fn test_encrypt_bundle() {
+        use fast_log::plugin::file_split::Packer;
+        use fast_log::plugin::packer::{decrypt_file, EncryptPacker, ZipPacker};
+        let dir = "target/test_encrypt_bundle/";
+        let _ = remove_dir_all(dir);
+        std::fs::create_dir_all(dir).unwrap();
+        let key = [7u8; 32];
+        let packer = EncryptPacker::new(key).after(ZipPacker::new().bundle_daily());
+        assert_eq!(packer.bundle_format(), Some("YYYY-MM-DD"));
+        for hour in ["01", "02"] {
+            let log_path = format!("{}temp2020-01-01T{}-00-00.000000.log", dir, hour);
+            std::fs::write(&log_path, hour).unwrap();
+            assert_eq!(packer.archive_path(&log_path), format!("{}temp2020-01-01.zip.enc", dir));
+            assert!(packer.do_pack(std::fs::File::open(&log_path).unwrap(), &log_path).unwrap());
+        }
+        //the members of the bundle are kept, no plain bundle is left
+        assert!(!std::path::Path::new(&format!("{}temp2020-01-01.zip", dir)).exists());
+        let zip_path = format!("{}plain.zip", dir);
+        decrypt_file(&key, &format!("{}temp2020-01-01.zip.enc", dir), &zip_path).unwrap();
+        let zip = zip::ZipArchive::new(std::fs::File::open(&zip_path).unwrap()).unwrap();
+        assert_eq!(zip.len(), 2);
+        let matcher = ArchiveMatcher::new("temp.log").pack_name(packer.pack_name()).bundle_format("YYYY-MM-DD");
+        assert!(matcher.parse("temp2020-01-01.zip.enc").unwrap().time.is_some());
+        let _ = remove_dir_all(dir);
+    }
+
+    #[cfg(feature = "mmap")]
+    #[test]
+    fn test_mmap_file() {
+        use fast_log::plugin::file_split::SplitFile;
+        use fast_log::plugin::mmap_file::MmapFile;
+        for (dir, rotate_type) in [("target/test_mmap_rename/", RotateType::Rename), ("target/test_mmap_copy/", RotateType::Copy)] {
+            let _ = remove_dir_all(dir);
+            let temp = format!("{}temp.log", dir);
+            let mut appender = FileSplitAppender::new::<MmapFile>(
+                dir,
+                Box::new(Rolling::new(RollingType::BySize(LogSize::KB(64)))),
+                Box::new(KeepType::All),
+                Box::new(LogPacker {}),
+            )
+                .unwrap()
+                .rotate_type(rotate_type);
+            //preallocated to the size limit
+            assert_eq!(std::fs::metadata(&temp).unwrap().len(), 64 * 1024);
+            let mut r = record(SystemTime::now());
+            r.formated = "first\n".to_string();
+            appender.do_logs(&[r.clone()]);
+            //trimmed before rolling
+            appender.send_pack("temp-old.log".to_string(), None);
+            assert_eq!(std::fs::read_to_string(format!("{}temp-old.log", dir)).unwrap(), "first\n");
+            assert_eq!(std::fs::metadata(&temp).unwrap().len(), 64 * 1024);
+            r.formated = "second\n".to_string();
+            appender.do_logs(&[r]);
+            //trimmed on exit
+            appender.do_logs(&[FastLogRecord { command: Command::CommandExit, ..record(SystemTime::now()) }]);
+            assert_eq!(std::fs::read_to_string(&temp).unwrap(), "second\n");
+            let _ = remove_dir_all(dir);
+        }
+        //the written length is recovered after an crash
+        let dir = "target/test_mmap_recover/";
+        let _ = remove_dir_all(dir);
+        std::fs::create_dir_all(dir).unwrap();
+        let mut data = b"crash\n".to_vec();
+        data.resize(4096, 0);
+        std::fs::write(format!("{}temp.log", dir), data).unwrap();
+        let file = MmapFile::new(&format!("{}temp.log", dir)).unwrap();
+        assert_eq!(file.len(), 6);
+        file.write(b"next\n").unwrap();
+        drop(file);
+        assert_eq!(std::fs::read_to_string(format!("{}temp.log", dir)).unwrap(), "crash\nnext\n");
+        let _ = remove_dir_all(dir);
+    }
+
+    #[cfg(feature = "mmap")]
+    #[test]
+    fn test_mmap_truncated() {
+        use fast_log::plugin::file_split::SplitFile;
+        use fast_log::plugin::mmap_file::MmapFile;
+        let dir = "target/test_mmap_truncated/";
+        let _ = remove_dir_all(dir);
+        std::fs::create_dir_all(dir).unwrap();
+        let path = format!("{}temp.log", dir);
+        let file = MmapFile::new(&path).unwrap();
+        file.write(b"first\n").unwrap();
+        //truncated by others, remapped by the flush of next tick
+        std::fs::OpenOptions::new().write(true).open(&path).unwrap().set_len(0).unwrap();
+        file.flush();
+        file.write(b"second\n").unwrap();
+        assert_eq!(file.len(), 7);
+        drop(file);
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "second\n");
+        let _ = remove_dir_all(dir);
+    }
+
+    #[cfg(feature = "manifest")]
+    #[test]
+    fn test_manifest() {
+        use fast_log::plugin::manifest;
+        let dir = "target/test_manifest/";
+        let _ = remove_dir_all(dir);
+        let mut appender = FileSplitAppender::new::<RawFile>(
+            dir,
+            Box::new(Rolling::new(RollingType::BySize(LogSize::MB(1)))),
+            Box::new(KeepType::All),
+            Box::new(LogPacker {}),
+        )
+            .unwrap();
+        let mut r = record(SystemTime::now());
+        r.formated = "2026-10-18 12:00:00.000000  [INFO] a\n".to_string();
+        let mut r2 = r.clone();
+        r2.formated = "2026-10-18 12:00:01.000000  [ERROR] [a.rs:1] b\n".to_string();
+        appender.do_logs(&[r, r2]);
+        appender.send_pack("temp2020-01-01T00-00-00.000000.log".to_string(), None);
+        sleep(Duration::from_millis(500));
+        let (entries, _) = manifest::read(dir).unwrap();
+        let entry = &entries["temp2020-01-01T00-00-00.000000.log"];
+        assert_eq!(entry.first, "2026-10-18 12:00:00.000000");
+        assert_eq!(entry.last, "2026-10-18 12:00:01.000000");
+        assert_eq!(entry.levels["INFO"], 1);
+        assert_eq!(entry.levels["ERROR"], 1);
+        assert!(manifest::verify(dir).unwrap().is_ok());
+        std::fs::write(format!("{}temp2020-01-01T00-00-00.000000.log", dir), "changed").unwrap();
+        assert_eq!(manifest::verify(dir).unwrap().corrupted.len(), 1);
+        std::fs::remove_file(format!("{}temp2020-01-01T00-00-00.000000.log", dir)).unwrap();
+        assert_eq!(manifest::verify(dir).unwrap().missing.len(), 1);
+        let _ = remove_dir_all(dir);
+    }
+
+    #[cfg(feature = "manifest")]
+    #[test]
+    fn test_manifest_index() {
+        use fast_log::plugin::manifest;
+        let dir = "target/test_manifest_index/";
+        let _ = remove_dir_all(dir);
+        let mut appender = FileSplitAppender::new::<RawFile>(
+            dir,
+            Box::new(Rolling::new(RollingType::BySize(LogSize::MB(1))).name_type(NameType::Index)),
+            Box::new(KeepType::KeepNum(2)),
+            Box::new(LogPacker {}),
+        )
+            .unwrap();
+        for i in 0..3 {
+            let mut r = record(SystemTime::now());
+            r.formated = format!("2026-10-18 12:00:0{}.000000  [INFO] a\n", i);
+            appender.do_logs(&[r]);
+            appender.send_pack(format!("temp2020-01-0{}T00-00-00.000000.log", i + 1), None);
+            sleep(Duration::from_millis(200));
+        }
+        //the shifted archives are renamed in the manifest, the removed one is compacted
+        let (entries, _) = manifest::read(dir).unwrap();
+        assert_eq!(entries.keys().collect::<Vec<_>>(), vec!["temp.log.1", "temp.log.2"]);
+        assert_eq!(entries["temp.log.1"].first, "2026-10-18 12:00:02.000000");
+        assert!(manifest::verify(dir).unwrap().is_ok());
+        let manifest = std::fs::read_to_string(format!("{}manifest.jsonl", dir)).unwrap();
+        assert_eq!(manifest.lines().count(), 2);
+        let _ = remove_dir_all(dir);
+    }
+
+    #[cfg(all(feature = "manifest", feature = "zip"))]
+    #[test]
+    fn test_manifest_bundle() {
+        use fast_log::plugin::manifest;
+        use fast_log::plugin::packer::ZipPacker;
+        let dir = "target/test_manifest_bundle/";
+        let _ = remove_dir_all(dir);
+        let mut appender = FileSplitAppender::new::<RawFile>(
+            dir,
+            Box::new(Rolling::new(RollingType::BySize(LogSize::MB(1)))),
+            Box::new(KeepType::All),
+            Box::new(ZipPacker::new().bundle_daily()),
+        )
+            .unwrap();
+        for hour in ["01", "02"] {
+            let mut r = record(SystemTime::now());
+            r.formated = format!("2026-10-18 {}:00:00.000000  [INFO] a\n", hour);
+            appender.do_logs(&[r]);
+            appender.send_pack(format!("temp2020-01-01T{}-00-00.000000.log", hour), None);
+            sleep(Duration::from_millis(200));
+        }
+        //one entry for each member of the bundle
+        let (entries, _) = manifest::read(dir).unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries["temp2020-01-01.zip#temp2020-01-01T01-00-00.000000.log"].first, "2026-10-18 01:00:00.000000");
+        assert_eq!(entries["temp2020-01-01.zip#temp2020-01-01T02-00-00.000000.log"].first, "2026-10-18 02:00:00.000000");
+        let report = manifest::verify(dir).unwrap();
+        assert!(report.is_ok());
+        assert_eq!(report.ok, vec!["temp2020-01-01.zip"]);
+        let _ = remove_dir_all(dir);
+    }
+
+    #[test]
+    fn test_archive_matcher() {
+        let matcher = ArchiveMatcher::new("temp.log").pack_name("zip");
+        let archive = matcher.parse("temp2020-01-02T00-00-00.000000.zip").unwrap();
+        assert_eq!(archive.time, Some(SystemTime::from(DateTime::parse("YYYY-MM-DD", "2020-01-02").unwrap())));
+        assert!(matcher.parse("temp2020-01-02T00-00-00.000000-1.log").is_some());
+        assert_eq!(matcher.parse("temp.log.3.zip").unwrap().index, Some(3));
+        assert!(matcher.parse("temp.log").is_none());
+        assert!(matcher.parse("temp_audit2020-01-02T00-00-00.000000.zip").is_none());
+        assert!(matcher.parse("temp2020-01-02T00-00-00.000000.lz4").is_none());
+        assert!(matcher.parse("temp.log.bak").is_none());
+    }
+
+    #[test]
+    fn test_keep_time_by_name() {
+        let dir = "target/test_keep_time_by_name/";
+        let _ = remove_dir_all(dir);
+        std::fs::create_dir_all(dir).unwrap();
+        let now = DateTime::now().format("YYYY-MM-DDThh-mm-ss.000000");
+        std::fs::write(format!("{}temp{}.log", dir, now), "new").unwrap();
+        std::fs::write(format!("{}temp2020-01-01T00-00-00.000000.log", dir), "old").unwrap();
+        std::fs::write(format!("{}temp_audit.log", dir), "other").unwrap();
+        //the file is just created, but the name is old
+        assert_eq!(KeepType::KeepTime(Duration::from_secs(3600)).do_keep(dir, "temp.log"), 1);
+        assert!(std::path::Path::new(&format!("{}temp{}.log", dir, now)).exists());
+        assert!(std::path::Path::new(&format!("{}temp_audit.log", dir)).exists());
+        let _ = remove_dir_all(dir);
+    }
+
+    #[test]
+    fn test_archive_index() {
+        assert_eq!(archive_index("temp.log", "temp.log.2.zip"), Some((2, ".zip".to_string())));
+        assert_eq!(archive_index("temp.log", "temp.12.log"), Some((12, ".log".to_string())));
+        assert_eq!(archive_index("temp.log", "temp.log"), None);
+        assert_eq!(archive_index("temp.log", "temp.3"), None);
+        assert_eq!(archive_index("temp.log", "temp2026-10-18T12-00-00.000000.log"), None);
+    }
+
+    #[test]
+    fn test_name_template() {
+        let time = DateTime::from_timestamp(0);
+        let mut template = NameTemplate::new("{base}-{seq}-{date}{suffix}").date_format("YYYY-MM-DD");
+        assert_eq!(template.render("temp.log", &time), "temp-0-1970-01-01.log");
+        assert_eq!(template.render("temp.log", &time), "temp-1-1970-01-01.log");
+        let mut template = NameTemplate::new("{base}-{pid}-{date}{suffix}").date_format("YYYY-MM-DD");
+        let first = template.render("temp.log", &time);
+        let second = template.render("temp.log", &time);
+        assert_eq!(first, format!("temp-{}-1970-01-01.log", std::process::id()));
+        //the collision is resolved by the appender
+        assert_eq!(second, first);
+    }
+
+    #[test]
+    fn test_restart_same_date() {
+        let dir = "target/test_restart_same_date/";
+        let _ = remove_dir_all(dir);
+        let template = NameTemplate::new("{base}{date}{suffix}").date_format("YYYY-MM-DD");
+        for _ in 0..2 {
+            let mut appender = FileSplitAppender::new::<RawFile>(
+                dir,
+                Box::new(Rolling::new(RollingType::BySize(LogSize::B(10))).name_template(template.clone())),
+                Box::new(KeepType::KeepNum(10)),
+                Box::new(LogPacker {}),
+            )
+                .unwrap();
+            for _ in 0..2 {
+                let mut r = record(SystemTime::now());
+                r.formated = "0123456789abcdef\n".to_string();
+                appender.do_logs(&[r]);
+            }
+            appender.do_logs(&[FastLogRecord { command: Command::CommandExit, ..record(SystemTime::now()) }]);
+            sleep(Duration::from_millis(200));
+        }
+        let date = DateTime::now().format("YYYY-MM-DD");
+        let mut names: Vec<String> = std::fs::read_dir(dir)
+            .unwrap()
+            .flatten()
+            .map(|x| x.file_name().to_string_lossy().to_string())
+            .filter(|x| x != "temp.log" && x.starts_with("temp"))
+            .collect();
+        names.sort();
+        //the second process continues from the highest -N, only one -N is added
+        assert!(names.len() >= 4);
+        let mut expect: Vec<String> = (1..names.len()).map(|x| format!("temp{}-{}.log", date, x)).collect();
+        expect.push(format!("temp{}.log", date));
+        assert_eq!(names, expect);
+        let matcher = ArchiveMatcher::new("temp.log").template(template);
+        for name in names {
+            assert!(matcher.parse(&name).is_some(), "{}", name);
+        }
+        let _ = remove_dir_all(dir);
+    }
+
+    fn record(now: SystemTime) -> FastLogRecord {
+        FastLogRecord {
+            command: Command::CommandRecord,
+            level: Level::Info,
+            target: "".to_string(),
+            args: "".to_string(),
+            module_path: "".to_string(),
+            file: "".to_string(),
+            line: None,
+            now,
+            formated: "".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_composite_rolling() {
+        let mut rolling = CompositeRolling::any()
+            .push(Rolling::new(RollingType::ByDate(DateType::Day)))
+            .push(Rolling::new(RollingType::BySize(LogSize::B(10))));
+        let now = SystemTime::now();
+        assert_eq!(rolling.can(&LogPacker {}, "temp.log", 1, &record(now)), None);
+        let first = rolling.can(&LogPacker {}, "temp.log", 20, &record(now)).unwrap();
+        let second = rolling.can(&LogPacker {}, "temp.log", 20, &record(now)).unwrap();
+        assert_eq!(first, second);
+
+        let mut rolling = CompositeRolling::all()
+            .push(Rolling::new(RollingType::ByDate(DateType::Day)))
+            .push(Rolling::new(RollingType::BySize(LogSize::B(10))));
+        assert_eq!(rolling.can(&LogPacker {}, "temp.log", 20, &record(now)), None);
+        let tomorrow = now + Duration::from_secs(24 * 3600);
+        assert!(rolling.can(&LogPacker {}, "temp.log", 20, &record(tomorrow)).is_some());
+        assert_eq!(rolling.can(&LogPacker {}, "temp.log", 1, &record(tomorrow)), None);
+    }
+
+    #[test]
+    fn test_rolling_utc() {
+        let midnight = SystemTime::UNIX_EPOCH + Duration::from_secs(24 * 3600);
+        let mut rolling = Rolling::new(RollingType::ByDate(DateType::Day)).utc();
+        let before = midnight - Duration::from_secs(2);
+        assert_eq!(rolling.can_recover(&LogPacker {}, "temp.log", 1, before, &record(midnight - Duration::from_secs(1))), None);
+        let name = rolling.can(&LogPacker {}, "temp.log", 1, &record(midnight + Duration::from_secs(1))).unwrap();
+        assert!(name.contains("1970-01-01"), "{}", name);
+    }
+
     #[test]
     fn test_extract_file_name() {
         let p = "temp.log".extract_file_name();